@@ -0,0 +1,37 @@
+//! Commonly used numbers, each in its simplest form.
+//!
+//! These are functions rather than statics, as `clear_caches` would leave a static pointing at a dropped number. Every call returns the same cached number until then.
+
+use finite::construction::{dyadic, integer};
+use finite::SurrealFinite;
+
+/// Returns `0`, i.e. `{ | }`.
+pub fn zero() -> SurrealFinite {
+    SurrealFinite::zero()
+}
+
+/// Returns `1`, i.e. `{ 0 | }`.
+pub fn one() -> SurrealFinite {
+    SurrealFinite::one()
+}
+
+/// Returns `-1`, i.e. `{ | 0 }`.
+pub fn neg_one() -> SurrealFinite {
+    integer(-1)
+}
+
+/// Returns `1/2`, i.e. `{ 0 | 1 }`.
+///
+/// # Examples
+///
+/// ```
+/// assert!(surreal::consts::half() == surreal::ftos(0.5));
+/// ```
+pub fn half() -> SurrealFinite {
+    dyadic(1, 1)
+}
+
+/// Returns `2`, i.e. `{ 1 | }`.
+pub fn two() -> SurrealFinite {
+    integer(2)
+}
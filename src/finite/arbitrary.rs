@@ -0,0 +1,29 @@
+use proptest::arbitrary::Arbitrary;
+use proptest::collection;
+use proptest::prelude::any;
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+use super::SurrealFinite;
+
+/// The largest birthday of the numbers generated by `any_with::<SurrealFinite>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxBirthday(pub usize);
+
+impl Default for MaxBirthday {
+    // products of numbers born much later than this are slow to check
+    fn default() -> MaxBirthday {
+        MaxBirthday(4)
+    }
+}
+
+impl Arbitrary for SurrealFinite {
+    type Parameters = MaxBirthday;
+    type Strategy = BoxedStrategy<SurrealFinite>;
+
+    // numbers are sampled by their sign expansions, which shrink by dropping signs, so towards zero
+    fn arbitrary_with(max: MaxBirthday) -> BoxedStrategy<SurrealFinite> {
+        collection::vec(any::<bool>(), 0..=max.0)
+            .prop_map(|signs| SurrealFinite::from_sign_expansion(&signs))
+            .boxed()
+    }
+}
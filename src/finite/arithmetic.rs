@@ -1,24 +1,70 @@
-use std::collections::HashMap;
-use std::sync::Mutex;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::error::Error;
+use core::fmt;
 
-use super::{ftos, SurrealFinite};
+use sync::HashMap;
 
-// memoise these calculations?
+use super::construction::{
+    checked_dyadic_parts, dyadic, dyadic_parts, signed_integer, simplify, too_large, tree_search,
+    MAX_MAGNITUDE,
+};
+use super::{ftos, CacheStats, SurrealFinite, TryNewError};
 
-lazy_static! {
-    static ref ADD_MEMO: Mutex<HashMap<(u64, u64), u64>> = Mutex::new(HashMap::new());
-    static ref NEG_MEMO: Mutex<HashMap<u64, u64>> = Mutex::new(HashMap::new());
-    static ref MUL_MEMO: Mutex<HashMap<(u64, u64), u64>> = Mutex::new(HashMap::new());
+// results are simplified so that the option sets of later calculations stay small
+
+memos! {
+    static ADD_MEMO: HashMap<(u64, u64), u64> = HashMap::new();
+    static NEG_MEMO: HashMap<u64, u64> = HashMap::new();
+    static MUL_MEMO: HashMap<(u64, u64), u64> = HashMap::new();
+}
+
+pub fn clear() {
+    ADD_MEMO.clear();
+    NEG_MEMO.clear();
+    MUL_MEMO.clear();
+}
+
+pub fn stats(stats: &mut CacheStats) {
+    stats.add = ADD_MEMO.with(|memo| memo.len());
+    stats.neg = NEG_MEMO.with(|memo| memo.len());
+    stats.mul = MUL_MEMO.with(|memo| memo.len());
+}
+
+// the sum or product of two integers, or the negation of one, is built directly, as recursing through every smaller pair of integers takes time quadratic in their size
+fn integer_value(x: &SurrealFinite) -> Option<i64> {
+    if x.len_left() == 0 || x.len_right() == 0 {
+        Some(dyadic_parts(x).0) // a number with an empty set is always an integer
+    } else {
+        None
+    }
+}
+
+fn integer_op(
+    x: &SurrealFinite,
+    y: &SurrealFinite,
+    op: fn(i64, i64) -> Option<i64>,
+) -> Option<SurrealFinite> {
+    let n = op(integer_value(x)?, integer_value(y)?)?;
+    Some(signed_integer(n.unsigned_abs(), n < 0)) // not limited like integer, as the operands were already built
+}
+
+// the options of a sum, negation or product of numbers always form a number, so this only fails if an operand was a pseudo-number
+fn build(op: &str, left: Vec<SurrealFinite>, right: Vec<SurrealFinite>) -> SurrealFinite {
+    SurrealFinite::try_new(left, right).unwrap_or_else(|error| match error {
+        TryNewError::NotANumber(error) => panic!("{} of a pseudo-number: {}", op, error),
+        error => panic!("{} is too large: {}", op, error),
+    })
 }
 
 pub fn add(x: SurrealFinite, y: SurrealFinite) -> SurrealFinite {
-    {
-        let cache = ADD_MEMO.lock().unwrap();
-        if cache.contains_key(&(x.hash, y.hash)) {
-            return SurrealFinite {
-                hash: *cache.get(&(x.hash, y.hash)).unwrap(),
-            };
-        }
+    if let Some(hash) = ADD_MEMO.with(|memo| memo.get(&(x.hash, y.hash)).cloned()) {
+        return SurrealFinite { hash };
+    }
+
+    if let Some(result) = integer_op(&x, &y, i64::checked_add) {
+        ADD_MEMO.with_mut(|memo| memo.insert((x.hash, y.hash), result.hash));
+        return result;
     }
 
     let mut new_left: Vec<SurrealFinite> = Vec::new();
@@ -37,22 +83,21 @@ pub fn add(x: SurrealFinite, y: SurrealFinite) -> SurrealFinite {
         new_right.push(add(yr, x));
     }
 
-    let result = SurrealFinite::new(new_left, new_right).unwrap(); // doesnt need to be handled if x and y are non-pseudo
-    ADD_MEMO
-        .lock()
-        .unwrap()
-        .insert((x.hash, y.hash), result.hash);
+    let result = simplify(&build("sum", new_left, new_right));
+    ADD_MEMO.with_mut(|memo| memo.insert((x.hash, y.hash), result.hash));
     result
 }
 
 pub fn neg(x: SurrealFinite) -> SurrealFinite {
-    {
-        let cache = NEG_MEMO.lock().unwrap();
-        if cache.contains_key(&x.hash) {
-            return SurrealFinite {
-                hash: *cache.get(&x.hash).unwrap(),
-            };
-        }
+    if let Some(hash) = NEG_MEMO.with(|memo| memo.get(&x.hash).cloned()) {
+        return SurrealFinite { hash };
+    }
+
+    // an integer is negated directly, as recursing would nest as deep as its magnitude
+    if let Some(n) = integer_value(&x) {
+        let result = signed_integer(n.unsigned_abs(), n > 0);
+        NEG_MEMO.with_mut(|memo| memo.insert(x.hash, result.hash));
+        return result;
     }
 
     let mut new_left: Vec<SurrealFinite> = Vec::new();
@@ -65,53 +110,237 @@ pub fn neg(x: SurrealFinite) -> SurrealFinite {
         new_right.push(neg(xl));
     }
 
-    let result = SurrealFinite::new(new_left, new_right).unwrap();
-    NEG_MEMO.lock().unwrap().insert(x.hash, result.hash);
+    let result = build("negation", new_left, new_right);
+    NEG_MEMO.with_mut(|memo| memo.insert(x.hash, result.hash));
     result
 }
 
 pub fn mul(x: SurrealFinite, y: SurrealFinite) -> SurrealFinite {
-    {
-        let cache = MUL_MEMO.lock().unwrap();
-        if cache.contains_key(&(x.hash, y.hash)) {
-            return SurrealFinite {
-                hash: *cache.get(&(x.hash, y.hash)).unwrap(),
-            };
+    checked_mul(x, y, usize::MAX).unwrap()
+}
+
+/// Multiplies `x` by the integer `n` by doubling and adding, taking about `2 * log2(|n|)` additions.
+///
+/// Panics if the product is over `2^24` in magnitude, like `integer`, as the doubling would build it as deep as its magnitude.
+pub fn mul_int(x: SurrealFinite, n: i64) -> SurrealFinite {
+    // the float value is enough to tell, and is NaN for a pseudo-number, which the doubling reports instead
+    if x.stof().abs() * n.unsigned_abs() as f64 > MAX_MAGNITUDE as f64 {
+        too_large(format_args!("{} * {}", x.stof(), n));
+    }
+
+    let mut result = SurrealFinite::zero();
+    let mut doubled = x;
+    let mut m = n.unsigned_abs();
+    while m > 0 {
+        if m & 1 == 1 {
+            result = add(result, doubled);
+        }
+        m >>= 1;
+        if m > 0 {
+            doubled = add(doubled, doubled);
         }
     }
 
+    if n < 0 {
+        neg(result)
+    } else {
+        result
+    }
+}
+
+/// Multiplies `x` and `y`, or returns `None` if the product would need more than `max_depth` nested levels of recursion.
+///
+/// Products already in the memo table are returned regardless of the limit.
+pub fn checked_mul(x: SurrealFinite, y: SurrealFinite, max_depth: usize) -> Option<SurrealFinite> {
+    if let Some(hash) = MUL_MEMO.with(|memo| memo.get(&(x.hash, y.hash)).cloned()) {
+        return Some(SurrealFinite { hash });
+    }
+
+    if let Some(result) = integer_op(&x, &y, i64::checked_mul) {
+        MUL_MEMO.with_mut(|memo| memo.insert((x.hash, y.hash), result.hash));
+        return Some(result);
+    }
+
+    // multiplying by an integer only needs a few additions, so skip the product recursion
+    let scaled = match (integer_value(&x), integer_value(&y)) {
+        (_, Some(n)) => Some(mul_int(x, n)),
+        (Some(n), _) => Some(mul_int(y, n)),
+        _ => None,
+    };
+    if let Some(result) = scaled {
+        MUL_MEMO.with_mut(|memo| memo.insert((x.hash, y.hash), result.hash));
+        return Some(result);
+    }
+
+    let depth = max_depth.checked_sub(1); // only fails the product if an option actually needs a recursive call
+
     let mut new_left: Vec<SurrealFinite> = Vec::new();
     for xl in x.iter_left() {
         for yl in y.iter_left() {
-            new_left.push(add(add(mul(xl, y), mul(x, yl)), neg(mul(xl, yl))));
+            new_left.push(add(
+                add(checked_mul(xl, y, depth?)?, checked_mul(x, yl, depth?)?),
+                neg(checked_mul(xl, yl, depth?)?),
+            ));
         }
     }
     for xr in x.iter_right() {
         for yr in y.iter_right() {
-            new_left.push(add(add(mul(xr, y), mul(x, yr)), neg(mul(xr, yr))));
+            new_left.push(add(
+                add(checked_mul(xr, y, depth?)?, checked_mul(x, yr, depth?)?),
+                neg(checked_mul(xr, yr, depth?)?),
+            ));
         }
     }
 
     let mut new_right: Vec<SurrealFinite> = Vec::new();
     for xl in x.iter_left() {
         for yr in y.iter_right() {
-            new_right.push(add(add(mul(xl, y), mul(x, yr)), neg(mul(xl, yr))));
+            new_right.push(add(
+                add(checked_mul(xl, y, depth?)?, checked_mul(x, yr, depth?)?),
+                neg(checked_mul(xl, yr, depth?)?),
+            ));
         }
     }
     for xr in x.iter_right() {
         for yl in y.iter_left() {
-            new_right.push(add(add(mul(xr, y), mul(x, yl)), neg(mul(xr, yl))));
+            new_right.push(add(
+                add(checked_mul(xr, y, depth?)?, checked_mul(x, yl, depth?)?),
+                neg(checked_mul(xr, yl, depth?)?),
+            ));
         }
     }
 
-    let result = SurrealFinite::new(new_left, new_right).unwrap();
-    MUL_MEMO
-        .lock()
-        .unwrap()
-        .insert((x.hash, y.hash), result.hash);
-    result
+    let result = simplify(&build("product", new_left, new_right));
+    MUL_MEMO.with_mut(|memo| memo.insert((x.hash, y.hash), result.hash));
+    Some(result)
+}
+
+const MAX_DIV_BITS: usize = 32;
+
+/// An error produced when dividing surreal numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DivError {
+    /// The divisor was zero.
+    DivisionByZero,
+    /// The quotient is not a dyadic rational (e.g. `1 / 3`), so has no finite representation.
+    NotDyadic,
+    /// The quotient is over `2^24` in magnitude, so is too deep to build.
+    TooLarge,
+}
+
+impl fmt::Display for DivError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DivError::DivisionByZero => write!(f, "division by zero"),
+            DivError::NotDyadic => write!(f, "quotient is not a dyadic rational"),
+            DivError::TooLarge => write!(f, "quotient is too large to build"),
+        }
+    }
+}
+
+impl Error for DivError {}
+
+/// Divides two surreal numbers exactly.
+///
+/// Returns [`DivError::NotDyadic`] if the quotient is not a dyadic rational, or if it needs more than 32 binary places, and [`DivError::TooLarge`] if it is over `2^24` in magnitude.
+///
+/// The quotient is worked out from the exact values of its operands, so only the result is built.
+pub fn div(x: SurrealFinite, y: SurrealFinite) -> Result<SurrealFinite, DivError> {
+    if y == SurrealFinite::zero() {
+        return Err(DivError::DivisionByZero);
+    }
+
+    let (x, y) = if y < SurrealFinite::zero() {
+        (neg(x), neg(y))
+    } else {
+        (x, y)
+    };
+
+    match (checked_dyadic_parts(&x), checked_dyadic_parts(&y)) {
+        (Some(x), Some(y)) => dyadic_quotient(x, y),
+        // only reachable for numbers too deep to build in practice, and each candidate is one multiplication
+        _ => tree_search(|candidate| mul(y, *candidate).cmp(&x), MAX_DIV_BITS)
+            .ok_or(DivError::NotDyadic),
+    }
+}
+
+// (a / 2^p) / (b * 2^k / 2^q) for odd b is (a / b) / 2^(p + k - q), which is dyadic only if b divides a
+fn dyadic_quotient(
+    (numerator, denominator): (i64, u64),
+    (divisor, divisor_denominator): (i64, u64),
+) -> Result<SurrealFinite, DivError> {
+    let k = divisor.trailing_zeros();
+    let odd = i128::from(divisor >> k);
+    let numerator = i128::from(numerator);
+    if numerator % odd != 0 {
+        return Err(DivError::NotDyadic);
+    }
+
+    let mut quotient = numerator / odd;
+    let mut exponent = i64::from(denominator.trailing_zeros() + k)
+        - i64::from(divisor_denominator.trailing_zeros());
+    while exponent > 0 && quotient % 2 == 0 {
+        quotient /= 2;
+        exponent -= 1;
+    }
+    if exponent > MAX_DIV_BITS as i64 {
+        return Err(DivError::NotDyadic);
+    }
+    // a negative exponent only scales an integer up, by at most 63 places
+    let quotient = if exponent < 0 {
+        quotient << -exponent
+    } else {
+        quotient
+    };
+    if quotient.unsigned_abs() > u128::from(MAX_MAGNITUDE) << exponent.max(0) {
+        return Err(DivError::TooLarge);
+    }
+    let quotient = i64::try_from(quotient).expect("the quotient is bounded by 2^57");
+    Ok(dyadic(quotient, exponent.max(0) as u32))
+}
+
+/// Divides two surreal numbers approximately, by way of floating-point division.
+pub fn div_approx(x: SurrealFinite, y: SurrealFinite) -> Result<SurrealFinite, DivError> {
+    if y == SurrealFinite::zero() {
+        return Err(DivError::DivisionByZero); // ftos would never terminate on an infinite quotient
+    }
+
+    Ok(ftos(x.stof() / y.stof()))
+}
+
+// fills an n by n table with op of every ordered pair, computing each unordered pair once
+fn pairwise<F>(xs: &[SurrealFinite], op: F) -> Vec<SurrealFinite>
+where
+    F: Fn(SurrealFinite, SurrealFinite) -> SurrealFinite,
+{
+    let n = xs.len();
+    let mut table = vec![SurrealFinite::zero(); n * n];
+    for i in 0..n {
+        for j in i..n {
+            let result = op(xs[i], xs[j]);
+            table[i * n + j] = result;
+            table[j * n + i] = result;
+        }
+    }
+    table
+}
+
+/// Returns `xs[i] + xs[j]` for every `i` and `j`, at index `i * xs.len() + j`.
+///
+/// Addition is commutative, so each sum is computed only once for both orders of its operands.
+///
+/// # Examples
+///
+/// ```
+/// let xs = [surreal::ftos(0.5), surreal::ftos(2.0)];
+/// let sums = surreal::pairwise_sums(&xs);
+/// assert!(sums == vec![surreal::ftos(1.0), surreal::ftos(2.5), surreal::ftos(2.5), surreal::ftos(4.0)]);
+/// ```
+pub fn pairwise_sums(xs: &[SurrealFinite]) -> Vec<SurrealFinite> {
+    pairwise(xs, add)
 }
 
-pub fn div_approx(x: SurrealFinite, y: SurrealFinite) -> SurrealFinite {
-    ftos(x.stof() / y.stof())
+/// Returns `xs[i] * xs[j]` for every `i` and `j`, at index `i * xs.len() + j`, computing each product only once for both orders of its operands.
+pub fn pairwise_products(xs: &[SurrealFinite]) -> Vec<SurrealFinite> {
+    pairwise(xs, mul)
 }
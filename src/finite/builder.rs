@@ -0,0 +1,81 @@
+use super::SurrealFinite;
+
+/// Builds a surreal number by walking down the tree from zero one sign at a time, like [`SurrealFinite::from_sign_expansion`] but with the number reached so far always at hand.
+///
+/// # Examples
+///
+/// ```
+/// let mut builder = surreal::SurrealBuilder::new();
+/// builder.push(true).push(false);
+/// assert!(builder.value() == surreal::ftos(0.5));
+/// builder.push(true);
+/// assert!(builder.build() == surreal::ftos(0.75));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SurrealBuilder {
+    lower: Option<SurrealFinite>,
+    upper: Option<SurrealFinite>,
+    current: SurrealFinite,
+    len: usize,
+}
+
+impl SurrealBuilder {
+    /// Starts a walk at zero, the root of the tree.
+    pub fn new() -> SurrealBuilder {
+        SurrealBuilder {
+            lower: None,
+            upper: None,
+            current: SurrealFinite::zero(),
+            len: 0,
+        }
+    }
+
+    /// Steps to the right child of the current number for `true` (`+`), or to the left child for `false` (`-`).
+    pub fn push(&mut self, sign: bool) -> &mut SurrealBuilder {
+        if sign {
+            self.lower = Some(self.current);
+        } else {
+            self.upper = Some(self.current);
+        }
+        self.current = SurrealFinite::new_unchecked(
+            self.lower.into_iter().collect(),
+            self.upper.into_iter().collect(),
+        ); // the bounds are both ancestors on the walk, so the lower is always less than the upper
+        self.len += 1;
+        self
+    }
+
+    /// Returns the number reached so far, whose sign expansion is the signs pushed so far.
+    pub fn value(&self) -> SurrealFinite {
+        self.current
+    }
+
+    /// Returns the number of signs pushed so far, which is the birthday of the current number.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no signs have been pushed, so the current number is zero.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Finishes the walk, returning the number reached.
+    pub fn build(self) -> SurrealFinite {
+        self.current
+    }
+}
+
+impl Default for SurrealBuilder {
+    fn default() -> SurrealBuilder {
+        SurrealBuilder::new()
+    }
+}
+
+impl Extend<bool> for SurrealBuilder {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, signs: I) {
+        for sign in signs {
+            self.push(sign);
+        }
+    }
+}
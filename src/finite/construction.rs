@@ -1,101 +1,973 @@
-use std::collections::HashMap;
-use std::f64::EPSILON;
-use std::hash::{BuildHasher, Hash, Hasher};
-use std::sync::Mutex;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
 
-use super::SurrealFinite;
+use sync::{HashMap, RwLock};
 
-lazy_static! {
-    static ref CACHE: Mutex<HashMap<u64, SurrealStructure>> = Mutex::new(HashMap::new()); // serialise each value as part of key w/ serde
-    static ref LEQ_MEMO: Mutex<HashMap<(u64, u64), bool>> = Mutex::new(HashMap::new());
+use super::{CacheStats, SurrealFinite};
+
+memos! {
+    static LEQ_MEMO: HashMap<(u64, u64), bool> = HashMap::new();
+}
+
+global! {
+    static CACHE: RwLock<HashMap<u64, SurrealStructure>> = RwLock::new(HashMap::new()); // serialise each value as part of key w/ serde
+    static BIRTHDAY_MEMO: RwLock<HashMap<u64, u64>> = RwLock::new(HashMap::new());
+    static STOF_MEMO: RwLock<HashMap<u64, (f64, bool)>> = RwLock::new(HashMap::new()); // with whether the value is exact
+    static SMALL_INTEGERS: RwLock<Vec<SurrealFinite>> = RwLock::new(Vec::new()); // -SMALL_INTEGER_LIMIT..=SMALL_INTEGER_LIMIT, filled on first use
+    static FURTHEST_INTEGERS: RwLock<[Option<(u64, SurrealFinite)>; 2]> = RwLock::new([None, None]); // the largest magnitude built past the table, positive then negative
 }
 
-#[derive(Clone, Hash)]
+// the sets are shared, so reading them out of the cache doesn't copy them
+#[derive(Clone)]
 pub struct SurrealStructure {
-    pub left: Vec<SurrealFinite>,
-    pub right: Vec<SurrealFinite>,
+    pub left: Arc<[SurrealFinite]>,
+    pub right: Arc<[SurrealFinite]>,
 }
 
 impl SurrealStructure {
     pub fn new(left: Vec<SurrealFinite>, right: Vec<SurrealFinite>) -> SurrealStructure {
-        SurrealStructure { left, right }
+        SurrealStructure {
+            left: left.into(),
+            right: right.into(),
+        }
+    }
+}
+
+impl PartialEq for SurrealStructure {
+    // structural equality, unlike the value equality of SurrealFinite
+    fn eq(&self, other: &SurrealStructure) -> bool {
+        let same = |a: &[SurrealFinite], b: &[SurrealFinite]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.hash == y.hash)
+        };
+        same(&self.left, &other.left) && same(&self.right, &other.right)
+    }
+}
+
+impl Hash for SurrealStructure {
+    // hashes the member slots, agreeing with the structural equality above
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.left.len() as u64); // fixed width, so hashes don't depend on the platform
+        self.left.iter().for_each(|x| state.write_u64(x.hash));
+        state.write_u64(self.right.len() as u64);
+        self.right.iter().for_each(|x| state.write_u64(x.hash));
+    }
+}
+
+/// A 64-bit FNV-1a hasher. Unlike the randomly seeded `RandomState`, it gives every structure the same hash in every run and on every platform.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> StableHasher {
+        StableHasher(0xcbf2_9ce4_8422_2325)
     }
 }
 
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes()); // fixed byte order, so hashes don't depend on the platform
+    }
+}
+
+/// Interns a structure and returns its hash.
+///
+/// The hash depends only on the structure and the hashes of its options, so it is the same in every run, unless a collision with a structure inserted earlier forces it into a later slot.
 pub fn cache_insert(structure: SurrealStructure) -> u64 {
-    let mut hasher = CACHE.lock().unwrap().hasher().build_hasher();
-    structure.hash(&mut hasher); // use hashing from https://github.com/ElsevierSoftwareX/SOFTX_2018_184/blob/master/src/SurrealFinite.jl instead?
-    let hash: u64 = hasher.finish();
+    let mut hasher = StableHasher::new();
+    structure.hash(&mut hasher);
+
+    cache_insert_at(hasher.finish(), structure)
+}
+
+/// Inserts a structure into the cache at slot `hash`, probing past any slots held by a different structure, and returns the slot it ends up in.
+pub fn cache_insert_at(hash: u64, structure: SurrealStructure) -> u64 {
+    if let Some(existing) = CACHE.read().unwrap().get(&hash) {
+        if *existing == structure {
+            return hash; // most structures are already cached, so try without blocking other readers first
+        }
+    }
 
-    let mut cache = CACHE.lock().unwrap();
-    cache.entry(hash).or_insert(structure);
+    let mut hash = hash;
+    let mut cache = CACHE.write().unwrap(); // probe again, as another thread may have inserted in between
+    loop {
+        match cache.get(&hash) {
+            Some(existing) if *existing == structure => return hash,
+            Some(_) => hash = hash.wrapping_add(1), // collision
+            None => {
+                cache.insert(hash, structure);
+                return hash;
+            }
+        }
+    }
+}
+
+const EVICTED: &str = "surreal number is no longer cached (was it created before clear_caches?)";
+
+pub fn cache_left(hash: u64) -> Arc<[SurrealFinite]> {
+    let left = CACHE.read().unwrap().get(&hash).map(|s| s.left.clone()); // release the lock before panicking
+    left.expect(EVICTED)
+}
+
+pub fn cache_right(hash: u64) -> Arc<[SurrealFinite]> {
+    let right = CACHE.read().unwrap().get(&hash).map(|s| s.right.clone());
+    right.expect(EVICTED)
+}
+
+/// Iterates over a set taken from the cache, keeping it alive without copying it.
+pub fn iter_set(set: Arc<[SurrealFinite]>) -> impl DoubleEndedIterator<Item = SurrealFinite> {
+    (0..set.len()).map(move |i| set[i])
+}
+
+/// A read-only view of the sets of a number, sharing them with the cache, as returned by [`SurrealFinite::structure`].
+///
+/// It holds no lock, so numbers can be built and compared while it is alive, and it stays valid even if the cache is cleared.
+#[derive(Clone)]
+pub struct Structure {
+    left: Arc<[SurrealFinite]>,
+    right: Arc<[SurrealFinite]>,
+}
+
+impl Structure {
+    /// The left set.
+    pub fn left(&self) -> &[SurrealFinite] {
+        &self.left
+    }
+
+    /// The right set.
+    pub fn right(&self) -> &[SurrealFinite] {
+        &self.right
+    }
+
+    /// The left and right sets.
+    pub fn sets(&self) -> (&[SurrealFinite], &[SurrealFinite]) {
+        (&self.left, &self.right)
+    }
+}
+
+pub fn cache_structure(hash: u64) -> Structure {
+    let structure = CACHE.read().unwrap().get(&hash).cloned(); // release the lock before panicking
+    let SurrealStructure { left, right } = structure.expect(EVICTED);
+    Structure { left, right }
+}
+
+pub fn cache_sets(hash: u64) -> (Vec<SurrealFinite>, Vec<SurrealFinite>) {
+    let sets = CACHE
+        .read()
+        .unwrap()
+        .get(&hash)
+        .map(|s| (s.left.to_vec(), s.right.to_vec()));
+    sets.expect(EVICTED)
+}
+
+/// Returns every number in the cache, sorted by hash, so the order doesn't depend on how the map lays them out.
+pub fn cached() -> Vec<SurrealFinite> {
+    let mut hashes: Vec<u64> = CACHE.read().unwrap().keys().cloned().collect(); // released before the caller compares them
+    hashes.sort_unstable();
+    hashes
+        .into_iter()
+        .map(|hash| SurrealFinite { hash })
+        .collect()
+}
 
-    hash
+pub fn is_cached(hash: u64) -> bool {
+    CACHE.read().unwrap().contains_key(&hash)
 }
 
-pub fn cache_left(hash: u64) -> Vec<SurrealFinite> {
-    CACHE.lock().unwrap().get(&hash).unwrap().left.clone()
+/// Returns the sizes of the left and right sets without copying them.
+pub fn cache_lens(hash: u64) -> (usize, usize) {
+    let lens = CACHE
+        .read()
+        .unwrap()
+        .get(&hash)
+        .map(|s| (s.left.len(), s.right.len()));
+    lens.expect(EVICTED)
 }
 
-pub fn cache_right(hash: u64) -> Vec<SurrealFinite> {
-    CACHE.lock().unwrap().get(&hash).unwrap().right.clone()
+pub fn clear() {
+    CACHE.write().unwrap().clear();
+    LEQ_MEMO.clear();
+    BIRTHDAY_MEMO.write().unwrap().clear();
+    STOF_MEMO.write().unwrap().clear();
+    SMALL_INTEGERS.write().unwrap().clear();
+    *FURTHEST_INTEGERS.write().unwrap() = [None, None];
 }
 
+pub fn stats(stats: &mut CacheStats) {
+    stats.structures = CACHE.read().unwrap().len();
+    stats.leq = LEQ_MEMO.with(|memo| memo.len());
+    stats.birthday = BIRTHDAY_MEMO.read().unwrap().len();
+    stats.stof = STOF_MEMO.read().unwrap().len();
+}
+
+/// Conway's fundamental relation: `x <= y` unless some left option of `x` is at least `y`, or `x` is at least some right option of `y`.
+///
+/// The comparison operators are defined by it, so `x <= y` is `leq(x, y)`, and `x == y` is `leq(x, y) && leq(y, x)`. Results are memoised.
+///
+/// # Examples
+///
+/// ```
+/// let (zero, one) = (surreal::SurrealFinite::zero(), surreal::SurrealFinite::one());
+/// assert!(surreal::leq(&zero, &one) && !surreal::leq(&one, &zero));
+/// assert!(surreal::leq(&zero, &zero));
+/// ```
 pub fn leq(x: &SurrealFinite, y: &SurrealFinite) -> bool {
+    if let Some(result) = LEQ_MEMO.with(|memo| memo.get(&(x.hash, y.hash)).cloned()) {
+        return result;
+    }
+
+    // x <= y unless some xl has y <= xl, or some yr has yr <= x; pairs still to be decided are kept on an explicit stack rather than recursed into
+    let mut pending: Vec<(u64, u64)> = vec![(x.hash, y.hash)];
+
+    while let Some(&(x, y)) = pending.last() {
+        let x = SurrealFinite { hash: x };
+        let y = SurrealFinite { hash: y };
+
+        let options: Vec<(u64, u64)> = x
+            .iter_left()
+            .map(|xl| (y.hash, xl.hash))
+            .chain(y.iter_right().map(|yr| (yr.hash, x.hash)))
+            .collect();
+
+        let mut result = Some(true);
+        LEQ_MEMO.with(|memo| {
+            for pair in options {
+                match memo.get(&pair) {
+                    Some(true) => {
+                        result = Some(false);
+                        break;
+                    }
+                    Some(false) => {}
+                    None => {
+                        result = None;
+                        pending.push(pair);
+                        break;
+                    }
+                }
+            }
+        });
+
+        if let Some(result) = result {
+            LEQ_MEMO.with_mut(|memo| memo.insert((x.hash, y.hash), result));
+            pending.pop();
+        }
+    }
+
+    LEQ_MEMO.with(|memo| memo[&(x.hash, y.hash)])
+}
+
+/// Walks down the tree of surreal numbers from zero, using `cmp` to decide whether the target lies to the left or right of each node.
+///
+/// Returns the first (and therefore simplest) node for which `cmp` reports `Equal`, or `None` if more than `max_fraction_bits` steps are taken once the integer part is known.
+pub fn tree_search<F>(cmp: F, max_fraction_bits: usize) -> Option<SurrealFinite>
+where
+    F: FnMut(&SurrealFinite) -> Ordering,
+{
+    tree_search_from(None, None, cmp, max_fraction_bits)
+}
+
+/// Continues a `tree_search` as if it had already reached the nodes `lower` and `upper` on its way down, so that a known prefix of the path need not be compared again.
+pub fn tree_search_from<F>(
+    mut lower: Option<SurrealFinite>,
+    mut upper: Option<SurrealFinite>,
+    mut cmp: F,
+    max_fraction_bits: usize,
+) -> Option<SurrealFinite>
+where
+    F: FnMut(&SurrealFinite) -> Ordering,
+{
+    let mut fraction_bits = 0;
+
+    loop {
+        let node =
+            SurrealFinite::new_unchecked(lower.into_iter().collect(), upper.into_iter().collect());
+
+        match cmp(&node) {
+            Ordering::Equal => return Some(node),
+            Ordering::Less => lower = Some(node), // target is to the right
+            Ordering::Greater => upper = Some(node),
+        }
+
+        if lower.is_some() && upper.is_some() {
+            if fraction_bits == max_fraction_bits {
+                return None;
+            }
+            fraction_bits += 1;
+        }
+    }
+}
+
+/// Returns the simplest (earliest-born) surreal number equal in value to `x`.
+pub fn simplify(x: &SurrealFinite) -> SurrealFinite {
+    tree_search(|node| node.cmp(x), usize::MAX).unwrap() // a finite surreal always has a finite birthday
+}
+
+/// Returns the simplest (earliest-born) surreal number lying strictly between `lo` and `hi`, or `None` if `lo >= hi`.
+///
+/// By the simplicity theorem this is the value of `{ lo | hi }`, returned in its simplest form.
+///
+/// # Examples
+///
+/// ```
+/// let x = surreal::simplest_between(surreal::ftos(0.25), surreal::ftos(3.0)).unwrap();
+/// assert!(x == surreal::ftos(1.0) && x.birthday() == 1);
+/// assert!(surreal::simplest_between(surreal::ftos(1.0), surreal::ftos(1.0)).is_none());
+/// ```
+pub fn simplest_between(lo: SurrealFinite, hi: SurrealFinite) -> Option<SurrealFinite> {
+    if lo >= hi {
+        return None;
+    }
+
+    let cmp = |node: &SurrealFinite| {
+        if *node <= lo {
+            Ordering::Less
+        } else if *node >= hi {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    };
+    tree_search(cmp, usize::MAX) // the search ends by the birthday of { lo | hi }
+}
+
+/// Returns the value of `{ lower | upper }`, i.e. the simplest number strictly between the bounds, for numbers whose options may not be adjacent to it in the tree.
+fn simplest_value(lower: Option<f64>, upper: Option<f64>) -> f64 {
+    match (lower, upper) {
+        (None, None) => 0.0,
+        (Some(l), None) if l < 0.0 => 0.0,
+        (Some(l), None) => (l as i64 + 1) as f64, // the least integer above l
+        (None, Some(u)) if u > 0.0 => 0.0,        // not negated, which would give -0.0
+        (None, Some(u)) => -simplest_value(Some(-u), None),
+        (Some(l), Some(u)) if l >= u => f64::NAN, // a pseudo-number has no value
+        (Some(l), Some(u)) if l < 0.0 && u > 0.0 => 0.0,
+        (Some(l), Some(u)) if u <= 0.0 => -simplest_value(Some(-u), Some(-l)),
+        (Some(l), Some(u)) => {
+            let next = (l as i64 + 1) as f64;
+            if next < u {
+                return next; // an integer lies between, and the least one is the simplest
+            }
+
+            // otherwise bisect between the integers either side of l
+            let (mut a, mut b) = (next - 1.0, next);
+            loop {
+                let mid = (a + b) / 2.0;
+                if mid == a || mid == b {
+                    return mid; // no float lies strictly between the bounds, so the value rounds to one of them
+                } else if mid <= l {
+                    a = mid;
+                } else if mid >= u {
+                    b = mid;
+                } else {
+                    return mid;
+                }
+            }
+        }
+    }
+}
+
+pub fn stof(x: &SurrealFinite) -> f64 {
+    stof_with_exactness(x).0
+}
+
+/// Returns `stof(x)` if it is exactly the value of `x`, and `None` if rounding crept in on the way.
+///
+/// An inexact value can be far off, as when a rounded option lands on a simpler number than the true one, so it says nothing about order.
+pub fn stof_exact(x: &SurrealFinite) -> Option<f64> {
+    match stof_with_exactness(x) {
+        (value, true) => Some(value),
+        (_, false) => None,
+    }
+}
+
+fn stof_with_exactness(x: &SurrealFinite) -> (f64, bool) {
+    if let Some(value) = STOF_MEMO.read().unwrap().get(&x.hash) {
+        return *value;
+    }
+
+    // values are computed bottom-up from an explicit stack, as deep numbers would overflow the call stack
+    let mut values: HashMap<u64, (f64, bool)> = HashMap::new();
+    {
+        let memo = STOF_MEMO.read().unwrap();
+        let known = |values: &HashMap<u64, (f64, bool)>, s: &SurrealFinite| {
+            values.get(&s.hash).or_else(|| memo.get(&s.hash)).cloned()
+        };
+        let mut pending = vec![*x];
+
+        while let Some(&x) = pending.last() {
+            let left = x.iter_left().last();
+            let right = x.iter_right().next();
+
+            let unknown: Vec<SurrealFinite> = left
+                .iter()
+                .chain(right.iter())
+                .filter(|s| known(&values, s).is_none())
+                .cloned()
+                .collect();
+            if !unknown.is_empty() {
+                pending.extend(unknown);
+                continue;
+            }
+
+            let lower = left.map(|l| known(&values, &l).unwrap());
+            let upper = right.map(|r| known(&values, &r).unwrap());
+            let value = simplest_value(lower.map(|l| l.0), upper.map(|u| u.0));
+
+            // exact bounds give an exact value, as long as it is a float strictly between them that still has room for every integer
+            let exact = lower.is_none_or(|(l, exact)| exact && l < value)
+                && upper.is_none_or(|(u, exact)| exact && value < u)
+                && value.abs() < MAX_EXACT_INTEGER as f64;
+            values.insert(x.hash, (value, exact));
+            pending.pop();
+        }
+    }
+
+    let value = values[&x.hash];
+    STOF_MEMO.write().unwrap().extend(values);
+    value
+}
+
+/// Returns the path taken from zero to the simplest form of `x`, with `true` for each step right (`+`) and `false` for each step left (`-`).
+pub fn sign_expansion(x: &SurrealFinite) -> Vec<bool> {
+    let mut signs = Vec::new();
+    tree_search(
+        |node| {
+            let ordering = node.cmp(x);
+            if ordering != Ordering::Equal {
+                signs.push(ordering == Ordering::Less);
+            }
+            ordering
+        },
+        usize::MAX,
+    )
+    .unwrap(); // a finite surreal always has a finite birthday
+    signs
+}
+
+/// Follows a sign expansion from zero, returning the node it ends at.
+pub fn from_sign_expansion(signs: &[bool]) -> SurrealFinite {
+    let mut signs = signs.iter();
+    tree_search(
+        |_| match signs.next() {
+            Some(true) => Ordering::Less,
+            Some(false) => Ordering::Greater,
+            None => Ordering::Equal,
+        },
+        usize::MAX,
+    )
+    .unwrap() // the walk ends when the signs run out
+}
+
+/// Returns the day on which the value of `x` is first created, i.e. the number of steps taken to reach its simplest form from zero.
+pub fn birthday(x: &SurrealFinite) -> u64 {
     {
-        let cache = LEQ_MEMO.lock().unwrap();
-        if cache.contains_key(&(x.hash, y.hash)) {
-            return *cache.get(&(x.hash, y.hash)).unwrap();
+        let cache = BIRTHDAY_MEMO.read().unwrap();
+        if cache.contains_key(&x.hash) {
+            return *cache.get(&x.hash).unwrap();
         }
     }
 
-    let mut result = true;
+    let mut days = 0;
+    tree_search(
+        |node| {
+            let ordering = node.cmp(x);
+            if ordering != Ordering::Equal {
+                days += 1;
+            }
+            ordering
+        },
+        usize::MAX,
+    );
+
+    BIRTHDAY_MEMO.write().unwrap().insert(x.hash, days);
+    days
+}
 
-    for xl in x.iter_left() {
-        if leq(y, &xl) {
-            result = false;
-            break;
+const SMALL_INTEGER_LIMIT: u64 = 64;
+
+/// Returns every surreal number born on or before day `day`, in ascending order.
+///
+/// Each day adds a number between each pair of neighbours and one at either end, so day `n` has `2^(n + 1) - 1` numbers, and anything past day 20 or so is impractical.
+///
+/// # Examples
+///
+/// ```
+/// let day_one = surreal::generation(1);
+/// assert!(day_one == vec![surreal::ftos(-1.0), surreal::ftos(0.0), surreal::ftos(1.0)]);
+/// ```
+pub fn generation(day: u32) -> Vec<SurrealFinite> {
+    let mut v = vec![SurrealFinite::zero()];
+    for _ in 0..day {
+        let mut w = Vec::with_capacity(2 * v.len() + 1);
+        w.push(SurrealFinite::new_unchecked(vec![], vec![v[0]]));
+        for pair in v.windows(2) {
+            w.push(pair[0]);
+            w.push(SurrealFinite::new_unchecked(vec![pair[0]], vec![pair[1]]));
+        }
+        w.push(v[v.len() - 1]);
+        w.push(SurrealFinite::new_unchecked(vec![v[v.len() - 1]], vec![]));
+        v = w;
+    }
+    v
+}
+
+/// Returns the same numbers as [`generation`], building the numbers of each day in parallel.
+///
+/// The caches are shared between threads, and a number built on two threads at once is still only cached once, so the results are identical.
+#[cfg(feature = "rayon")]
+pub fn generation_par(day: u32) -> Vec<SurrealFinite> {
+    use rayon::prelude::*;
+
+    let mut v = vec![SurrealFinite::zero()];
+    for _ in 0..day {
+        let between: Vec<SurrealFinite> = v
+            .par_windows(2)
+            .map(|pair| SurrealFinite::new_unchecked(vec![pair[0]], vec![pair[1]]))
+            .collect();
+
+        let mut w = Vec::with_capacity(2 * v.len() + 1);
+        w.push(SurrealFinite::new_unchecked(vec![], vec![v[0]]));
+        for (x, y) in v.iter().zip(&between) {
+            w.push(*x);
+            w.push(*y);
         }
+        w.push(v[v.len() - 1]);
+        w.push(SurrealFinite::new_unchecked(vec![v[v.len() - 1]], vec![]));
+        v = w;
     }
+    v
+}
+
+/// Returns the integer `n` in its simplest form, taken from a table of small integers where possible.
+///
+/// Panics if `n` is over `2^24` in magnitude, like [`ftos`].
+///
+/// # Examples
+///
+/// ```
+/// assert!(surreal::integer(-3) == surreal::ftos(-3.0));
+/// ```
+pub fn integer(n: i64) -> SurrealFinite {
+    checked_integer(n.unsigned_abs(), n < 0).unwrap_or_else(|| too_large(n))
+}
+
+/// Builds the integer with the given magnitude and sign, or returns `None` if the magnitude is over `2^24`.
+pub fn checked_integer(magnitude: u64, negative: bool) -> Option<SurrealFinite> {
+    if magnitude <= MAX_MAGNITUDE {
+        Some(signed_integer(magnitude, negative))
+    } else {
+        None
+    }
+}
 
-    if result {
-        for yr in y.iter_right() {
-            if leq(&yr, x) {
-                result = false;
-                break;
+// the panic of every conversion from a number whose simplest form would be too deep to build
+pub fn too_large<T: fmt::Display>(n: T) -> ! {
+    panic!(
+        "{} is too large to convert to a surreal number, as its simplest form would be nested as deep as its magnitude",
+        n
+    )
+}
+
+/// Builds the integer with the given magnitude and sign, starting from whichever integer already built is nearest and stepping along the tree from there.
+///
+/// Every form of an integer `n` is nested at least `n` deep, so the first integer of a magnitude costs time linear in it; later ones only cost their distance from the furthest built so far.
+pub fn signed_integer(magnitude: u64, negative: bool) -> SurrealFinite {
+    let offset = SMALL_INTEGER_LIMIT as usize;
+    let table_index = |m: u64| {
+        if negative {
+            offset - m as usize
+        } else {
+            offset + m as usize
+        }
+    };
+
+    if SMALL_INTEGERS.read().unwrap().is_empty() {
+        let mut table = vec![SurrealFinite::zero(); 2 * offset + 1];
+        for m in 1..=offset {
+            table[offset + m] = SurrealFinite::new_unchecked(vec![table[offset + m - 1]], vec![]);
+            table[offset - m] = SurrealFinite::new_unchecked(vec![], vec![table[offset - m + 1]]);
+        }
+        *SMALL_INTEGERS.write().unwrap() = table;
+    }
+    if magnitude <= SMALL_INTEGER_LIMIT {
+        return SMALL_INTEGERS.read().unwrap()[table_index(magnitude)];
+    }
+
+    let side = negative as usize;
+    let furthest = FURTHEST_INTEGERS.read().unwrap()[side];
+    let (start, mut x) = match furthest {
+        // the predecessor of {x|} or {|x} is its only option, so stepping back reads the cache instead of hashing
+        Some((far, mut x))
+            if far < magnitude || far - magnitude < magnitude - SMALL_INTEGER_LIMIT =>
+        {
+            for _ in magnitude..far {
+                x = if negative {
+                    cache_right(x.hash)[0]
+                } else {
+                    cache_left(x.hash)[0]
+                };
             }
+            (far.min(magnitude), x)
+        }
+        _ => (
+            SMALL_INTEGER_LIMIT,
+            SMALL_INTEGERS.read().unwrap()[table_index(SMALL_INTEGER_LIMIT)],
+        ),
+    };
+    for _ in start..magnitude {
+        x = if negative {
+            SurrealFinite::new_unchecked(vec![], vec![x])
+        } else {
+            SurrealFinite::new_unchecked(vec![x], vec![])
+        };
+    }
+    if start < magnitude {
+        let mut furthest = FURTHEST_INTEGERS.write().unwrap();
+        if furthest[side].is_none_or(|(far, _)| far < magnitude) {
+            furthest[side] = Some((magnitude, x));
         }
     }
+    x
+}
 
-    LEQ_MEMO.lock().unwrap().insert((x.hash, y.hash), result);
-    result
+pub fn floor(x: &SurrealFinite) -> SurrealFinite {
+    let mut n = SurrealFinite::zero();
+    if n <= *x {
+        loop {
+            let next = SurrealFinite::new_unchecked(vec![n], vec![]);
+            if next > *x {
+                return n;
+            }
+            n = next;
+        }
+    } else {
+        loop {
+            n = SurrealFinite::new_unchecked(vec![], vec![n]);
+            if n <= *x {
+                return n;
+            }
+        }
+    }
 }
 
-/// Converts a floating-point number into a surreal number with finite sets.
-pub fn ftos(f: f64) -> SurrealFinite {
-    // add lazy evaluation?
-    let zero = SurrealFinite::zero();
-    let one = SurrealFinite::new(vec![zero], vec![]).unwrap();
-    let neg_one = SurrealFinite::new(vec![], vec![zero]).unwrap();
+/// Walks down the tree like `tree_search`, passing `cmp` the exact value of each node as `(numerator, exponent)`, meaning `numerator / 2^exponent` in lowest terms.
+///
+/// Each node on the walk is either one step past an integer bound or the midpoint of its two bounds, so the value can be tracked without calling `stof`.
+///
+/// The numerator is tracked in 128 bits, so values whose parts don't fit in 64 bits are still found exactly; past 128 bits the value is `None` from then on.
+fn dyadic_search<F>(mut cmp: F) -> (SurrealFinite, Option<(i128, u32)>)
+where
+    F: FnMut(&SurrealFinite, Option<(i128, u32)>) -> Ordering,
+{
+    let mut lower: Option<(i128, u32)> = None;
+    let mut upper: Option<(i128, u32)> = None;
+    let mut value = Some((0, 0));
 
-    let mut increment = if f > 0.0 { one } else { neg_one };
-    let mut large_bound = zero;
-    let mut small_bound = zero;
+    let node = tree_search(
+        |node| {
+            value = value.and_then(|_| match (lower, upper) {
+                (None, None) => Some((0, 0)),
+                (Some((l, _)), None) => Some((l.checked_add(1)?, 0)), // only integers are reached with a single bound
+                (None, Some((u, _))) => Some((u.checked_sub(1)?, 0)),
+                (Some((l, le)), Some((u, ue))) => {
+                    let exponent = le.max(ue);
+                    let sum =
+                        shl_exact(l, exponent - le)?.checked_add(shl_exact(u, exponent - ue)?)?;
+                    Some((sum, exponent + 1))
+                }
+            });
+            if let Some((ref mut n, ref mut e)) = value {
+                while *e > 0 && *n % 2 == 0 {
+                    *n /= 2;
+                    *e -= 1;
+                }
+            }
+
+            let ordering = cmp(node, value);
+            match ordering {
+                Ordering::Less => lower = value,
+                Ordering::Greater => upper = value,
+                Ordering::Equal => {}
+            }
+            ordering
+        },
+        usize::MAX,
+    )
+    .unwrap(); // the caller's target always has a finite birthday
 
-    while (f - large_bound.stof()).abs() > EPSILON {
-        // i.e. the best approximation with a finite float
-        large_bound = small_bound;
-        while f.abs() > large_bound.stof().abs() {
-            small_bound = large_bound;
-            large_bound += increment;
+    (node, value)
+}
+
+// shifts n left, or returns None if any of its bits would be lost
+fn shl_exact(n: i128, shift: u32) -> Option<i128> {
+    let shifted = n.checked_shl(shift)?;
+    if shifted >> shift == n {
+        Some(shifted)
+    } else {
+        None
+    }
+}
+
+/// Returns the value of `x` as `(numerator, denominator)` in lowest terms, with the denominator a power of two.
+///
+/// Panics if either part doesn't fit in 64 bits.
+pub fn dyadic_parts(x: &SurrealFinite) -> (i64, u64) {
+    checked_dyadic_parts(x).expect("the value of the number doesn't fit in 64-bit dyadic parts")
+}
+
+/// Returns the value of `x` like `dyadic_parts`, or `None` if either part doesn't fit in 64 bits.
+pub fn checked_dyadic_parts(x: &SurrealFinite) -> Option<(i64, u64)> {
+    let (numerator, exponent) = dyadic_search(|node, _| node.cmp(x)).1?;
+    let numerator = i64::try_from(numerator).ok()?;
+    let denominator = 1u64.checked_shl(exponent)?;
+    Some((numerator, denominator))
+}
+
+/// Converts `x` to the nearest `f32`, rounding its exact value once, to nearest with ties to even.
+pub fn stof32(x: &SurrealFinite) -> f32 {
+    if let Some(value) = stof_exact(x) {
+        return value as f32; // the f64 is the exact value, so narrowing it is the only rounding
+    }
+
+    let (numerator, exponent) = match dyadic_search(|node, _| node.cmp(x)).1 {
+        Some(value) => value,
+        None => return stof(x) as f32, // rounded twice, but only numbers over 128 bits deep get here
+    };
+    let magnitude = numerator.unsigned_abs();
+    if magnitude == 0 {
+        return 0.0;
+    }
+
+    // keep as many bits as an f32 holds at this magnitude, fewer once it is subnormal
+    let bits = i64::from(128 - magnitude.leading_zeros());
+    let leading = bits - 1 - i64::from(exponent); // the binary exponent of the leading bit
+    let precision = (leading + 150).min(f32::MANTISSA_DIGITS.into());
+    let dropped = bits - precision;
+
+    let kept = if dropped <= 0 {
+        magnitude
+    } else if dropped > bits {
+        0 // less than half of the smallest subnormal
+    } else {
+        let kept = magnitude.checked_shr(dropped as u32).unwrap_or(0);
+        let rest = magnitude - kept.checked_shl(dropped as u32).unwrap_or(0);
+        let half = 1u128 << (dropped - 1);
+        if rest > half || (rest == half && kept % 2 == 1) {
+            kept + 1
+        } else {
+            kept
         }
+    };
+
+    // kept fits in an f32's mantissa, so scaling it in an f64 and narrowing is exact, apart from overflowing to infinity
+    let scale = dropped.max(0) - i64::from(exponent);
+    let value = if scale > 1023 {
+        f32::INFINITY
+    } else {
+        // 2^scale, built from its bits as powi needs std; kept is zero wherever scale is below -1022
+        let power = f64::from_bits(((1023 + scale.max(-1022)) as u64) << 52);
+        (kept as f64 * power) as f32
+    };
+    if numerator < 0 {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Builds the simplest surreal number equal to `numerator / 2^exponent`.
+///
+/// Panics if the denominator in lowest terms is over `2^126`, as the values on the way are tracked in 128 bits.
+pub fn dyadic(numerator: i64, exponent: u32) -> SurrealFinite {
+    let (mut target, mut exponent) = (i128::from(numerator), exponent);
+    while exponent > 0 && target % 2 == 0 {
+        target /= 2;
+        exponent -= 1;
+    }
+    assert!(
+        exponent <= 126,
+        "the denominator of {} / 2^{} is too large to build",
+        target,
+        exponent
+    );
+
+    // every node on the way has a denominator no larger than the target's, and a value within one of it, so neither shift overflows
+    dyadic_search(|_, value| {
+        let (n, e) = value.expect("values on the way to the target fit in 128 bits");
+        (n << (exponent - e)).cmp(&target)
+    })
+    .0
+}
+
+const MAX_EXACT_INTEGER: u64 = 1 << f64::MANTISSA_DIGITS; // beyond this, floats skip integers
 
-        if increment > zero {
-            increment = SurrealFinite::new(vec![zero], vec![increment]).unwrap();
+/// Walks down the tree towards `f` until a node within `tolerance` of it is found, giving up after `max_fraction_bits` binary places, or straight away if `f` isn't finite or is over `2^24` in magnitude.
+///
+/// The integers on the way are built directly rather than compared one by one, and the value of the result is memoised for `stof`.
+pub fn float_search(f: f64, tolerance: f64, max_fraction_bits: usize) -> Option<SurrealFinite> {
+    // add lazy evaluation?
+    // the search visits nodes in a fixed order, so the value of each node can be tracked alongside it instead of calling stof
+    if !is_convertible(f) {
+        return None; // the search would pass too many integers to finish, or never settle at all
+    }
+
+    let mut lower: Option<f64> = None;
+    let mut upper: Option<f64> = None;
+    let mut start = (None, None);
+
+    // the search would pass every integer between zero and the integer part of f, so start from the one before it
+    let whole = f.abs() as u64;
+    if whole >= 1 {
+        let node = signed_integer(whole - 1, f < 0.0);
+        if f > 0.0 {
+            lower = Some((whole - 1) as f64);
+            start.0 = Some(node);
         } else {
-            increment = SurrealFinite::new(vec![increment], vec![zero]).unwrap();
+            upper = Some(-((whole - 1) as f64));
+            start.1 = Some(node);
+        }
+    }
+
+    let mut found = 0.0;
+    let x = tree_search_from(
+        start.0,
+        start.1,
+        |_| {
+            let value = match (lower, upper) {
+                (None, None) => 0.0,
+                (Some(l), None) => l + 1.0,
+                (None, Some(u)) => u - 1.0,
+                (Some(l), Some(u)) => (l + u) / 2.0,
+            };
+
+            if (f - value).abs() <= tolerance {
+                found = value;
+                Ordering::Equal
+            } else if value < f {
+                lower = Some(value);
+                Ordering::Less
+            } else {
+                upper = Some(value);
+                Ordering::Greater
+            }
+        },
+        max_fraction_bits,
+    )?;
+
+    STOF_MEMO.write().unwrap().insert(x.hash, (found, true)); // every node on the way to a float is a float itself
+    Some(x)
+}
+
+/// Converts a floating-point number into the surreal number of exactly the same value, so `ftos(f).stof() == f` for every `f` it accepts.
+///
+/// The simplest form of an integer `n` is nested `n` deep, so building one takes time and cache memory linear in `n`, about half a second per million in release builds; numbers already built past, or near the largest built, are cheap.
+///
+/// Panics if `f` is not finite, or its magnitude is over `2^24`; [`is_convertible`] tells whether it would. For the simplest number close to `f`, see [`ftos_with_tolerance`].
+pub fn ftos(f: f64) -> SurrealFinite {
+    ftos_with_tolerance(f, 0.0)
+}
+
+/// The largest magnitude that floats and integers are converted from, beyond which building the number would take minutes and gigabytes.
+pub const MAX_MAGNITUDE: u64 = 1 << 24;
+
+/// Returns whether `ftos` accepts `f`, i.e. whether it is finite and at most `2^24` in magnitude.
+pub fn is_convertible(f: f64) -> bool {
+    f.is_finite() && f.abs() <= MAX_MAGNITUDE as f64
+}
+
+/// Enough binary places to reach any finite `f64` exactly, including subnormals.
+const MAX_FTOS_FRACTION_BITS: usize = 1074;
+
+/// Converts a floating-point number like `ftos`, returning the simplest surreal number within `tolerance * max(|f|, 1)` of `f`.
+///
+/// A tolerance of zero (or less) asks for `f` exactly. Either way the search stops after enough binary places to reach any `f64`, so it always terminates.
+///
+/// Panics if `f` is not finite, or its magnitude is over `2^24`, as the number would be too deep to build in practice.
+///
+/// # Examples
+///
+/// ```
+/// let x = surreal::ftos_with_tolerance(0.3, 0.01); // the simplest number within 0.01 of 0.3
+/// assert!(x == surreal::ftos(0.296875) && x.birthday() == 7);
+/// assert!(surreal::ftos_with_tolerance(1000.2, 0.001) == surreal::ftos(1000.0));
+/// ```
+pub fn ftos_with_tolerance(f: f64, tolerance: f64) -> SurrealFinite {
+    assert!(f.is_finite(), "cannot convert {} to a surreal number", f);
+    if f.abs() > MAX_MAGNITUDE as f64 {
+        too_large(f);
+    }
+
+    let tolerance = if tolerance > 0.0 {
+        tolerance * f.abs().max(1.0)
+    } else {
+        0.0
+    };
+    float_search(f, tolerance, MAX_FTOS_FRACTION_BITS).unwrap() // every finite float is reached within the cap
+}
+
+/// Lists every cached structure, children before parents, each as its two sets of indices into the structures listed before it.
+#[cfg(all(feature = "serde", feature = "std"))]
+pub fn structures() -> Vec<(Vec<u32>, Vec<u32>)> {
+    let cache = CACHE.read().unwrap();
+
+    // structures are ordered from an explicit stack, as deep numbers would overflow the call stack
+    let mut index: HashMap<u64, u32> = HashMap::new();
+    let mut order: Vec<u64> = Vec::new();
+    for &root in cache.keys() {
+        let mut pending = vec![(root, false)];
+        while let Some((hash, children_done)) = pending.pop() {
+            if index.contains_key(&hash) {
+                continue;
+            }
+            if children_done {
+                index.insert(hash, order.len() as u32);
+                order.push(hash);
+                continue;
+            }
+
+            pending.push((hash, true));
+            let structure = &cache[&hash];
+            for x in structure.left.iter().chain(structure.right.iter()) {
+                if !index.contains_key(&x.hash) {
+                    pending.push((x.hash, false));
+                }
+            }
         }
     }
 
-    large_bound
+    let indices = |set: &[SurrealFinite]| set.iter().map(|x| index[&x.hash]).collect();
+    order
+        .iter()
+        .map(|hash| (indices(&cache[hash].left), indices(&cache[hash].right)))
+        .collect()
+}
+
+/// Interns structures listed by `structures`, as if each had been built again, or returns `false` if one refers to a later one.
+#[cfg(all(feature = "serde", feature = "std"))]
+pub fn load_structures(structures: &[(Vec<u32>, Vec<u32>)]) -> bool {
+    let mut loaded: Vec<SurrealFinite> = Vec::with_capacity(structures.len());
+    for (left, right) in structures {
+        let set = |indices: &[u32]| -> Option<Vec<SurrealFinite>> {
+            indices
+                .iter()
+                .map(|&i| loaded.get(i as usize).cloned())
+                .collect()
+        };
+        match (set(left), set(right)) {
+            (Some(left), Some(right)) => loaded.push(SurrealFinite::new_unchecked(left, right)),
+            _ => return false,
+        }
+    }
+    true
 }
@@ -0,0 +1,91 @@
+use core::error::Error;
+use core::fmt;
+
+use super::SurrealFinite;
+
+/// A field law that failed to hold, as returned by [`verify_field_laws`], with the numbers it failed for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LawViolation {
+    /// `a + 0` is not `a`.
+    AdditiveIdentity { a: SurrealFinite },
+    /// `a + -a` is not zero.
+    AdditiveInverse { a: SurrealFinite },
+    /// `a * 1` is not `a`.
+    MultiplicativeIdentity { a: SurrealFinite },
+    /// `a * (1 / a)` is not one, for a power of two `a`, the reciprocal being found with `recip`.
+    MultiplicativeInverse { a: SurrealFinite },
+    /// `a * (b + c)` is not `a * b + a * c`.
+    Distributivity {
+        a: SurrealFinite,
+        b: SurrealFinite,
+        c: SurrealFinite,
+    },
+}
+
+impl fmt::Display for LawViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LawViolation::AdditiveIdentity { a } => write!(f, "a + 0 != a for a = {}", a.stof()),
+            LawViolation::AdditiveInverse { a } => write!(f, "a + -a != 0 for a = {}", a.stof()),
+            LawViolation::MultiplicativeIdentity { a } => {
+                write!(f, "a * 1 != a for a = {}", a.stof())
+            }
+            LawViolation::MultiplicativeInverse { a } => {
+                write!(f, "a * (1 / a) != 1 for a = {}", a.stof())
+            }
+            LawViolation::Distributivity { a, b, c } => write!(
+                f,
+                "a * (b + c) != a * b + a * c for a = {}, b = {}, c = {}",
+                a.stof(),
+                b.stof(),
+                c.stof()
+            ),
+        }
+    }
+}
+
+impl Error for LawViolation {}
+
+/// Checks the field laws over every number, pair and triple drawn from `numbers`, returning the first that fails.
+///
+/// Besides the identities and additive inverses, it checks `a * (1 / a) == 1` wherever `a` is a power of two, and so has a dyadic reciprocal, and distributivity over every triple, so the cost is cubic in the number of numbers.
+///
+/// # Examples
+///
+/// ```
+/// assert!(surreal::verify_field_laws(&surreal::generation(2)).is_ok());
+/// ```
+pub fn verify_field_laws(numbers: &[SurrealFinite]) -> Result<(), LawViolation> {
+    let zero = SurrealFinite::zero();
+    let one = SurrealFinite::one();
+
+    for &a in numbers {
+        if a + zero != a {
+            return Err(LawViolation::AdditiveIdentity { a });
+        }
+        if a + -a != zero {
+            return Err(LawViolation::AdditiveInverse { a });
+        }
+        if a * one != a {
+            return Err(LawViolation::MultiplicativeIdentity { a });
+        }
+        // only powers of two have dyadic reciprocals, so the rest are skipped
+        if let Some(recip) = a.recip() {
+            if a * recip != one {
+                return Err(LawViolation::MultiplicativeInverse { a });
+            }
+        }
+    }
+
+    for &a in numbers {
+        for &b in numbers {
+            let ab = a * b;
+            for &c in numbers {
+                if a * (b + c) != ab + a * c {
+                    return Err(LawViolation::Distributivity { a, b, c });
+                }
+            }
+        }
+    }
+    Ok(())
+}
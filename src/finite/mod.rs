@@ -1,26 +1,225 @@
-use std::cmp::Ordering;
-use std::fmt;
-use std::hash::{Hash, Hasher};
-use std::ops;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::error::Error;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter;
+use core::ops;
+use core::str::FromStr;
+use core::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 
+#[cfg(feature = "proptest")]
+mod arbitrary;
 mod arithmetic;
-mod construction;
+mod builder;
+pub(crate) mod construction;
+mod laws;
+#[cfg(feature = "num-traits")]
+mod numeric;
+pub(crate) mod parse;
+#[cfg(feature = "num-bigint")]
+mod rational;
+#[cfg(feature = "serde")]
+mod serialization;
 
-pub use self::arithmetic::div_approx;
-pub use self::construction::ftos;
-use self::construction::{cache_insert, cache_left, cache_right, SurrealStructure};
+#[cfg(feature = "proptest")]
+pub use self::arbitrary::MaxBirthday;
+pub use self::arithmetic::{div, div_approx, pairwise_products, pairwise_sums, DivError};
+pub use self::builder::SurrealBuilder;
+#[cfg(feature = "rayon")]
+pub use self::construction::generation_par;
+use self::construction::{cache_insert, cache_left, cache_right, iter_set, SurrealStructure};
+pub use self::construction::{
+    ftos, ftos_with_tolerance, generation, integer, leq, simplest_between, Structure,
+};
+pub use self::laws::{verify_field_laws, LawViolation};
+pub use self::parse::ParseSurrealError;
+use super::game::SurrealOrdering;
 use super::infinite::{SurrealElement, SurrealInfinite};
 
+/// The number of entries in each of the global caches, as returned by [`cache_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The left and right sets of every number constructed so far.
+    pub structures: usize,
+    /// Memoised results of `<=`.
+    pub leq: usize,
+    /// Memoised birthdays.
+    pub birthday: usize,
+    /// Memoised results of `stof`.
+    pub stof: usize,
+    /// Memoised sums.
+    pub add: usize,
+    /// Memoised negations.
+    pub neg: usize,
+    /// Memoised products.
+    pub mul: usize,
+}
+
+/// Empties every global cache, reclaiming their memory.
+///
+/// Every `SurrealFinite` created before the call is invalidated, as it refers to its sets through the cache; using one afterwards panics.
+///
+/// With the `thread-local-memos` feature, the memo tables of other threads are emptied the next time each of them uses its own.
+pub fn clear_caches() {
+    construction::clear();
+    arithmetic::clear();
+}
+
+/// Writes the structure of every cached number to `w` as JSON, so that a later process can `load_cache` it instead of building the numbers again.
+///
+/// Only the structures are written, not the memoised results of comparisons and arithmetic. As the slot of each structure depends only on its sets, numbers built after loading refer to the same slots they would have had in the dumping process.
+#[cfg(all(feature = "serde", feature = "std"))]
+pub fn dump_cache<W: std::io::Write>(w: W) -> std::io::Result<()> {
+    serialization::dump(w)
+}
+
+/// Reads structures written by `dump_cache` into the cache, alongside any already there.
+///
+/// Returns an error of kind `InvalidData` if the input wasn't written by `dump_cache`.
+///
+/// # Examples
+///
+/// ```
+/// let _ = surreal::ftos(0.625);
+/// let structures = surreal::cache_stats().structures;
+/// let mut dump = Vec::new();
+/// surreal::dump_cache(&mut dump).unwrap();
+///
+/// surreal::clear_caches();
+/// surreal::load_cache(&dump[..]).unwrap();
+/// assert!(surreal::cache_stats().structures == structures);
+/// ```
+#[cfg(all(feature = "serde", feature = "std"))]
+pub fn load_cache<R: std::io::Read>(r: R) -> std::io::Result<()> {
+    serialization::load(r)
+}
+
+/// Returns the number of entries currently held in each global cache.
+///
+/// With the `thread-local-memos` feature, the counts of memoised comparisons and arithmetic are those of the calling thread.
+pub fn cache_stats() -> CacheStats {
+    let mut stats = CacheStats::default();
+    construction::stats(&mut stats);
+    arithmetic::stats(&mut stats);
+    stats
+}
+
+const MAX_FLOAT_BITS: usize = 32;
+
+/// An error produced when a floating-point number has no exact surreal representation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryFromFloatError {
+    /// The number was `NaN` or infinite.
+    NotFinite,
+    /// The number needs more than 32 binary places, as with `1.0 / 3.0`.
+    NotDyadic,
+    /// The number is over `2^24` in magnitude, so its simplest form would be too deep to build in practice.
+    TooLarge,
+}
+
+impl fmt::Display for TryFromFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TryFromFloatError::NotFinite => write!(f, "number is not finite"),
+            TryFromFloatError::NotDyadic => write!(f, "number is not a short dyadic rational"),
+            TryFromFloatError::TooLarge => write!(f, "number is too large to build"),
+        }
+    }
+}
+
+impl Error for TryFromFloatError {}
+
+/// An error produced when the sets given to [`SurrealFinite::try_new`] form a pseudo-number, naming the pair of options that breaks the ordering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NotANumber {
+    /// The greatest number in the left set.
+    pub left: SurrealFinite,
+    /// The least number in the right set, which is not greater than `left`.
+    pub right: SurrealFinite,
+}
+
+impl fmt::Display for NotANumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "left option {} is not less than right option {}",
+            self.left.stof(),
+            self.right.stof()
+        )
+    }
+}
+
+impl Error for NotANumber {}
+
+/// An error produced when [`SurrealFinite::try_new`] can't build a number from the sets it was given.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryNewError {
+    /// The sets form a pseudo-number.
+    NotANumber(NotANumber),
+    /// The sets hold more options between them than [`set_max_options`] allows.
+    TooManyOptions {
+        /// The number of options given, counting duplicates.
+        options: usize,
+        /// The limit in force.
+        limit: usize,
+    },
+}
+
+impl fmt::Display for TryNewError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TryNewError::NotANumber(error) => error.fmt(f),
+            TryNewError::TooManyOptions { options, limit } => {
+                write!(f, "{} options exceed the limit of {}", options, limit)
+            }
+        }
+    }
+}
+
+impl Error for TryNewError {}
+
+impl From<NotANumber> for TryNewError {
+    fn from(error: NotANumber) -> TryNewError {
+        TryNewError::NotANumber(error)
+    }
+}
+
+static MAX_OPTIONS: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Limits how many options the sets given to [`SurrealFinite::try_new`] may hold between them, so that arithmetic on numbers with huge sets fails fast rather than exhausting memory.
+///
+/// The limit is global and unlimited by default. Sums and products build their results through `try_new`, so they panic on exceeding it, before the oversized sets are simplified or cached.
+///
+/// # Examples
+///
+/// ```
+/// surreal::set_max_options(1);
+/// let error = surreal::SurrealFinite::try_new(vec![surreal::ftos(0.0)], vec![surreal::ftos(1.0)]);
+/// assert!(error == Err(surreal::TryNewError::TooManyOptions { options: 2, limit: 1 }));
+/// surreal::set_max_options(usize::MAX);
+/// ```
+pub fn set_max_options(limit: usize) {
+    MAX_OPTIONS.store(limit, AtomicOrdering::Relaxed);
+}
+
+/// Returns the limit set by [`set_max_options`].
+pub fn max_options() -> usize {
+    MAX_OPTIONS.load(AtomicOrdering::Relaxed)
+}
+
 /// A representation of surreal numbers with finite sets.
-#[derive(Clone, Copy, Debug)] // should debug be derived here?
+#[derive(Clone, Copy)]
 pub struct SurrealFinite {
-    hash: u64,
+    pub(crate) hash: u64,
 }
 
 impl SurrealFinite {
     /// Creates a new surreal number given two finite vectors of surreal numbers. Each vector corresponds to a left set and a right set, where all numbers in the left set must be less than all numbers in the right set.
     ///
-    /// Returns `None` if any number in the left set is greater than any number in the right set.
+    /// Returns `None` if any number in the left set is greater than any number in the right set, or if the sets hold more options than [`set_max_options`] allows.
     ///
     /// Surreal numbers composed of non-finite sets are instead represented by [`SurrealInfinite`].
     ///
@@ -32,17 +231,60 @@ impl SurrealFinite {
     /// let neg_one = surreal::SurrealFinite::new(vec![], vec![zero]).unwrap();
     /// ```
     pub fn new(left: Vec<SurrealFinite>, right: Vec<SurrealFinite>) -> Option<SurrealFinite> {
-        let x = SurrealFinite::new_unchecked(left, right);
+        SurrealFinite::try_new(left, right).ok()
+    }
 
-        if !x.left_is_empty()
-            && !x.right_is_empty()
-            && x.iter_left().last() >= x.iter_right().next()
-        {
-            return None;
+    /// Creates a new surreal number like `new`, but on failure reports why, such as which left option is not less than which right option.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let zero = surreal::SurrealFinite::zero();
+    /// let one = surreal::SurrealFinite::one();
+    /// let error = surreal::SurrealFinite::try_new(vec![one], vec![zero]).unwrap_err();
+    /// assert!(error == surreal::TryNewError::NotANumber(surreal::NotANumber { left: one, right: zero }));
+    /// ```
+    pub fn try_new(
+        left: Vec<SurrealFinite>,
+        right: Vec<SurrealFinite>,
+    ) -> Result<SurrealFinite, TryNewError> {
+        let (options, limit) = (left.len() + right.len(), max_options());
+        if options > limit {
+            return Err(TryNewError::TooManyOptions { options, limit }); // counted before comparing any options
+        }
+        if let Some(error) = SurrealFinite::violation(&left, &right) {
+            return Err(error.into()); // checked first, so pseudo-numbers never reach the cache
         }
 
-        Some(x) // returns finite, non-pseudo surreal numbers
-                // use Err instead of Option?
+        Ok(SurrealFinite::new_unchecked(left, right)) // returns finite, non-pseudo surreal numbers
+    }
+
+    // the pair of options that makes the sets a pseudo-number, if any
+    fn violation(left: &[SurrealFinite], right: &[SurrealFinite]) -> Option<NotANumber> {
+        match (left.iter().max(), right.iter().min()) {
+            (Some(&max), Some(&min)) if max >= min => Some(NotANumber {
+                left: max,
+                right: min,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns whether `new` would accept the given left and right sets, that is, whether every number in the left set is less than every number in the right set.
+    ///
+    /// Unlike `new`, nothing is inserted into the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let zero = surreal::SurrealFinite::zero();
+    /// let one = surreal::SurrealFinite::one();
+    /// assert!(surreal::SurrealFinite::is_valid_number(&[zero], &[one]));
+    /// assert!(!surreal::SurrealFinite::is_valid_number(&[one], &[zero]));
+    /// assert!(!surreal::SurrealFinite::is_valid_number(&[zero], &[zero])); // a pseudo-number
+    /// ```
+    pub fn is_valid_number(left: &[SurrealFinite], right: &[SurrealFinite]) -> bool {
+        SurrealFinite::violation(left, right).is_none()
     }
 
     fn new_unchecked(mut left: Vec<SurrealFinite>, mut right: Vec<SurrealFinite>) -> SurrealFinite {
@@ -82,43 +324,509 @@ impl SurrealFinite {
     }
 
     pub fn iter_left(&self) -> impl Iterator<Item = SurrealFinite> {
-        cache_left(self.hash).into_iter()
+        iter_set(cache_left(self.hash))
     }
 
     pub fn iter_right(&self) -> impl Iterator<Item = SurrealFinite> {
-        cache_right(self.hash).into_iter()
+        iter_set(cache_right(self.hash))
     }
 
-    fn left_is_empty(&self) -> bool {
-        cache_left(self.hash).is_empty()
+    /// Iterates over every option, the left set followed by the right set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let half = surreal::ftos(0.5); // { 0 | 1 }
+    /// assert!(half.iter_options().eq(vec![surreal::ftos(0.0), surreal::ftos(1.0)]));
+    /// ```
+    pub fn iter_options(&self) -> impl Iterator<Item = SurrealFinite> {
+        let (left, right) = self.sets();
+        left.into_iter().chain(right)
     }
 
-    fn right_is_empty(&self) -> bool {
-        cache_right(self.hash).is_empty()
+    /// Returns the number of elements in the left set, without copying it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let half = surreal::ftos(0.5); // { 0 | 1 }
+    /// assert!(half.len_left() == 1 && half.len_right() == 1);
+    /// ```
+    pub fn len_left(&self) -> usize {
+        construction::cache_lens(self.hash).0
+    }
+
+    /// Returns the number of elements in the right set, without copying it.
+    pub fn len_right(&self) -> usize {
+        construction::cache_lens(self.hash).1
+    }
+
+    /// Shares the left and right sets with the cache, without copying them or holding its lock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let half = surreal::ftos(0.5); // { 0 | 1 }
+    /// let structure = half.structure();
+    /// assert!(structure.left().len() == 1 && structure.right().len() == 1);
+    /// ```
+    pub fn structure(&self) -> Structure {
+        construction::cache_structure(self.hash)
+    }
+
+    /// Returns copies of the left and right sets.
+    pub fn sets(&self) -> (Vec<SurrealFinite>, Vec<SurrealFinite>) {
+        construction::cache_sets(self.hash)
     }
 
     pub fn stof(&self) -> f64 {
-        match (self.left_is_empty(), self.right_is_empty()) {
-            (true, true) => 0.0,
-            (true, false) => self.iter_right().next().unwrap().stof() - 1.0,
-            (false, true) => self.iter_left().last().unwrap().stof() + 1.0,
-            (false, false) => {
-                (self.iter_left().last().unwrap().stof() + self.iter_right().next().unwrap().stof())
-                    / 2.0
+        construction::stof(self)
+    }
+
+    /// Returns every number currently in the cache that is equal in value to this one, including itself, however its sets are written.
+    ///
+    /// This is meant for debugging: it compares against the whole cache, so it is slow once many numbers have been built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (zero, one) = (surreal::SurrealFinite::zero(), surreal::SurrealFinite::one());
+    /// surreal::SurrealFinite::new(vec![zero], vec![surreal::ftos(2.0)]).unwrap();
+    /// let structures: Vec<String> = one.equivalent_cached().iter().map(|x| x.to_structure_string(Some(1))).collect();
+    /// assert!(structures.contains(&"{ { | } | { ... | } }".to_string())); // { 0 | 2 }
+    /// ```
+    pub fn equivalent_cached(&self) -> Vec<SurrealFinite> {
+        construction::cached()
+            .into_iter()
+            .filter(|x| x == self)
+            .collect()
+    }
+
+    /// Returns the nearest `f32` to this number, rounding its exact value to nearest with ties to even, and overflowing to infinity.
+    ///
+    /// Unlike `self.stof() as f32`, this rounds only once, so a value just past halfway between two `f32`s isn't first rounded onto the halfway point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(0.5).stof32() == 0.5);
+    /// assert!(surreal::ftos(1.0 + 2f64.powi(-25)).stof32() == 1.0);
+    /// ```
+    pub fn stof32(&self) -> f32 {
+        construction::stof32(self)
+    }
+
+    /// Returns the day on which this number is first created, i.e. the birthday of its simplest form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::SurrealFinite::zero().birthday() == 0);
+    /// assert!(surreal::ftos(0.5).birthday() == 2);
+    /// ```
+    pub fn birthday(&self) -> u64 {
+        construction::birthday(self)
+    }
+
+    /// Returns Conway's sign expansion of this number, with `true` for `+` and `false` for `-`.
+    ///
+    /// This is the path from zero down the tree of surreal numbers to the simplest form, so its length is the birthday, and numbers are ordered like their expansions compared lexicographically with `-` < end < `+`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(0.5).sign_expansion() == vec![true, false]);
+    /// assert!(surreal::ftos(-2.0).sign_expansion() == vec![false, false]);
+    /// ```
+    pub fn sign_expansion(&self) -> Vec<bool> {
+        construction::sign_expansion(self)
+    }
+
+    /// Builds the number with the given sign expansion, with `true` for `+` and `false` for `-`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let x = surreal::SurrealFinite::from_sign_expansion(&[true, false, true]);
+    /// assert!(x == surreal::ftos(0.75));
+    /// ```
+    pub fn from_sign_expansion(signs: &[bool]) -> SurrealFinite {
+        construction::from_sign_expansion(signs)
+    }
+
+    /// Writes the exact value of this number in binary, with no fractional point for integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(0.375).to_binary_string() == "0.011");
+    /// assert!(surreal::ftos(-6.0).to_binary_string() == "-110");
+    /// ```
+    pub fn to_binary_string(&self) -> String {
+        let signs = self.sign_expansion();
+        let negative = signs.first() == Some(&false);
+        let run = signs.iter().take_while(|&&s| s != negative).count();
+
+        // every sign after the first change halves the step to the last, so they're read off as bits with a final 1 closing the expansion
+        let mut s = String::from(if negative { "-" } else { "" });
+        if run == signs.len() {
+            s.push_str(&format!("{:b}", run));
+        } else {
+            s.push_str(&format!("{:b}.", run - 1));
+            for &sign in &signs[run + 1..] {
+                s.push(if sign != negative { '1' } else { '0' });
+            }
+            s.push('1');
+        }
+        s
+    }
+
+    /// Returns the simplest surreal number equal in value to this one, i.e. the unique representation with the lowest birthday.
+    ///
+    /// Numbers built from different sets can be equal in value, so this should be applied before comparing structures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let zero = surreal::SurrealFinite::zero();
+    /// let one = surreal::SurrealFinite::new(vec![zero], vec![surreal::ftos(4.0)]).unwrap();
+    /// assert!(one.canonicalize().iter_right().next().is_none());
+    /// ```
+    pub fn canonicalize(&self) -> SurrealFinite {
+        construction::simplify(self)
+    }
+
+    /// Tests whether this number is an integer, i.e. whether its simplest form has an empty left or right set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(-2.0).is_integer());
+    /// assert!(!surreal::ftos(0.5).is_integer());
+    /// ```
+    pub fn is_integer(&self) -> bool {
+        let simplest = self.canonicalize();
+        simplest.len_left() == 0 || simplest.len_right() == 0
+    }
+
+    /// Tests whether this number is a dyadic rational, which every finite surreal number is.
+    ///
+    /// Numbers like `1/3` are only born on day ω, so they can never be a `SurrealFinite`.
+    pub fn is_dyadic(&self) -> bool {
+        true
+    }
+
+    /// Returns this number as an exact fraction `(numerator, denominator)` in lowest terms, where the denominator is a power of two.
+    ///
+    /// Panics if either part doesn't fit in 64 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(-0.75).dyadic_parts() == (-3, 4));
+    /// assert!(surreal::ftos(5.0).dyadic_parts() == (5, 1));
+    /// ```
+    pub fn dyadic_parts(&self) -> (i64, u64) {
+        construction::dyadic_parts(self)
+    }
+
+    /// Compares two numbers like `cmp`, but with the result type shared with `SurrealGame::compare`; as numbers are totally ordered, the result is never `Confused`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use surreal::{ftos, SurrealOrdering};
+    ///
+    /// assert!(ftos(0.5).compare(&ftos(1.0)) == SurrealOrdering::Less);
+    /// ```
+    pub fn compare(&self, other: &SurrealFinite) -> SurrealOrdering {
+        SurrealOrdering::from(self.cmp(other))
+    }
+
+    /// Returns `1 / self`, or `None` if it isn't a dyadic rational, which is the case unless this number is a power of two or its negative.
+    ///
+    /// Conway's construction of the inverse would need infinitely many options for every other number, so the inverse of a power of two is built directly from its exact value instead.
+    ///
+    /// Also returns `None` for powers of two under `2^-24`, as their inverses are integers over `2^24`, too deep to build like those `integer` refuses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(4.0).recip() == Some(surreal::ftos(0.25)));
+    /// assert!(surreal::ftos(-0.5).recip() == Some(surreal::ftos(-2.0)));
+    /// assert!(surreal::ftos(3.0).recip().is_none());
+    /// ```
+    pub fn recip(&self) -> Option<SurrealFinite> {
+        let (numerator, denominator) = self.dyadic_parts();
+        if !numerator.unsigned_abs().is_power_of_two() {
+            return None; // zero, or a multiple of some odd number other than one
+        }
+
+        let unit = if numerator > 0 {
+            SurrealFinite::one()
+        } else {
+            -SurrealFinite::one()
+        };
+        let (a, e) = (
+            numerator.unsigned_abs().trailing_zeros(),
+            denominator.trailing_zeros(),
+        );
+        if e > a && e - a > construction::MAX_MAGNITUDE.trailing_zeros() {
+            return None;
+        }
+        Some(if e >= a {
+            unit << (e - a)
+        } else {
+            unit >> (a - e)
+        })
+    }
+
+    /// Multiplies two numbers, or returns `None` if the product would need more than `max_depth` nested levels of recursion.
+    ///
+    /// Multiplying numbers with high birthdays is expensive, so this lets callers bound the work done. Integer products and products computed before are always returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (x, y) = (surreal::ftos(0.375), surreal::ftos(-1.75));
+    /// assert!(x.checked_mul(y, 1).is_none());
+    /// assert!(x.checked_mul(y, 64) == Some(x * y));
+    /// ```
+    pub fn checked_mul(self, other: SurrealFinite, max_depth: usize) -> Option<SurrealFinite> {
+        arithmetic::checked_mul(self, other, max_depth)
+    }
+
+    /// Multiplies this number by the integer `n` through repeated doubling, which is much cheaper than the general product. Multiplying by an integer with `*` takes the same path.
+    ///
+    /// Panics if the product is over `2^24` in magnitude, like `integer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(0.5).mul_int(6) == surreal::ftos(3.0));
+    /// assert!(surreal::ftos(2.0).mul_int(-3) == surreal::ftos(-6.0));
+    /// ```
+    pub fn mul_int(&self, n: i64) -> SurrealFinite {
+        arithmetic::mul_int(*self, n)
+    }
+
+    /// Returns the smaller of the two numbers, or `self` if they are equal.
+    pub fn min(self, other: SurrealFinite) -> SurrealFinite {
+        Ord::min(self, other)
+    }
+
+    /// Returns the larger of the two numbers, or `other` if they are equal.
+    pub fn max(self, other: SurrealFinite) -> SurrealFinite {
+        Ord::max(self, other)
+    }
+
+    /// Restricts this number to the interval `[lo, hi]`.
+    ///
+    /// Panics if `lo > hi`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let x = surreal::ftos(2.0).clamp(surreal::ftos(0.0), surreal::ftos(1.0));
+    /// assert!(x == surreal::ftos(1.0));
+    /// ```
+    pub fn clamp(self, lo: SurrealFinite, hi: SurrealFinite) -> SurrealFinite {
+        assert!(lo <= hi, "clamp called with lo > hi");
+        Ord::clamp(self, lo, hi)
+    }
+
+    /// Returns the exact value of `(self + other) / 2`, in its simplest form.
+    ///
+    /// The midpoint is computed from the two numbers' dyadic parts, avoiding both the approximate `div_approx` and a multiplication, so it panics if either part doesn't fit in 64 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let x = surreal::ftos(2.0).midpoint(surreal::ftos(4.0));
+    /// assert!(x == surreal::ftos(3.0) && x.birthday() == 3);
+    /// ```
+    pub fn midpoint(self, other: SurrealFinite) -> SurrealFinite {
+        let ((a, a_denominator), (b, b_denominator)) = (self.dyadic_parts(), other.dyadic_parts());
+        let denominator = a_denominator.max(b_denominator);
+        let sum = i128::from(a) * i128::from(denominator / a_denominator)
+            + i128::from(b) * i128::from(denominator / b_denominator);
+
+        match i64::try_from(sum) {
+            Ok(sum) => construction::dyadic(sum, denominator.trailing_zeros() + 1),
+            Err(_) => (self + other) * construction::dyadic(1, 1), // only reachable for numbers too deep to build in practice
+        }
+    }
+
+    /// Returns the exact distance `|self - other|` between the two numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(3.0).abs_diff(surreal::ftos(5.0)) == surreal::ftos(2.0));
+    /// ```
+    pub fn abs_diff(self, other: SurrealFinite) -> SurrealFinite {
+        if self >= other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+
+    /// Returns how far this number's value as a float is from `target`, e.g. to bound the error of `ftos` or `div_approx`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(0.1).stof_error(0.1) <= f64::EPSILON);
+    /// ```
+    pub fn stof_error(&self, target: f64) -> f64 {
+        (self.stof() - target).abs()
+    }
+
+    /// Raises this number to the power `exp` by repeated squaring, using about `2 * log2(exp)` multiplications.
+    ///
+    /// By convention, any number (including zero) to the power zero is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(-0.5).pow(3) == surreal::ftos(-0.125));
+    /// assert!(surreal::SurrealFinite::zero().pow(0) == surreal::SurrealFinite::one());
+    /// ```
+    pub fn pow(&self, mut exp: u32) -> SurrealFinite {
+        let mut base = *self;
+        let mut result = SurrealFinite::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base *= base;
             }
         }
+        result
     }
 
-    // pub fn to_infinite(&self) -> SurrealInfinite {
-    //     SurrealInfinite::from_finite(*self)
-    // }
+    /// Returns the largest integer less than or equal to this number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(-0.5).floor() == surreal::ftos(-1.0));
+    /// ```
+    pub fn floor(&self) -> SurrealFinite {
+        construction::floor(self)
+    }
 
-    // pub fn to_element(&self) -> SurrealElement {
-    //     // lazy?
-    //     SurrealElement::Finite(*self)
-    // }
+    /// Returns the smallest integer greater than or equal to this number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(-0.5).ceil() == surreal::SurrealFinite::zero());
+    /// ```
+    pub fn ceil(&self) -> SurrealFinite {
+        -construction::floor(&-*self)
+    }
+
+    /// Returns the nearest integer to this number, rounding half-way cases away from zero like `f64::round`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(2.5).round() == surreal::ftos(3.0));
+    /// assert!(surreal::ftos(-2.5).round() == surreal::ftos(-3.0));
+    /// ```
+    pub fn round(&self) -> SurrealFinite {
+        let half =
+            SurrealFinite::new_unchecked(vec![SurrealFinite::zero()], vec![SurrealFinite::one()]);
+        if *self < SurrealFinite::zero() {
+            -construction::floor(&(half - *self))
+        } else {
+            construction::floor(&(*self + half))
+        }
+    }
+
+    /// Describes this number for exploration, as its value and birthday followed by its sets with each option shown as a value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(surreal::ftos(0.5).describe() == "0.5 (day 2, { 0 | 1 })");
+    /// assert!(surreal::ftos(0.0).describe() == "0 (day 0, { | })");
+    /// ```
+    pub fn describe(&self) -> String {
+        let (left, right) = self.sets();
+        let mut sets = String::from("{ ");
+        for x in left {
+            sets.push_str(&format!("{} ", x.stof()));
+        }
+        sets.push_str("| ");
+        for x in right {
+            sets.push_str(&format!("{} ", x.stof()));
+        }
+        sets.push('}');
+
+        format!("{} (day {}, {})", self.stof(), self.birthday(), sets)
+    }
+
+    /// Renders the full recursive structure of this number in Knuth's `{ L | R }` notation, so zero is `{ | }` and one is `{ { | } | }`.
+    ///
+    /// Options nested deeper than `max_depth` levels are shown as `...`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let two = surreal::ftos(2.0);
+    /// assert!(two.to_structure_string(None) == "{ { { | } | } | }");
+    /// assert!(two.to_structure_string(Some(1)) == "{ { ... | } | }");
+    /// ```
+    pub fn to_structure_string(&self, max_depth: Option<usize>) -> String {
+        enum Item {
+            Number(SurrealFinite, usize),
+            Text(&'static str),
+        }
+
+        // an explicit stack, so that long chains of options can't overflow the call stack
+        let mut s = String::new();
+        let mut pending = vec![Item::Number(*self, 0)];
+        while let Some(item) = pending.pop() {
+            match item {
+                Item::Text(t) => s.push_str(t),
+                Item::Number(_, depth) if max_depth.is_some_and(|max| depth > max) => {
+                    s.push_str("...")
+                }
+                Item::Number(x, depth) => {
+                    pending.push(Item::Text("}"));
+                    for r in x.iter_right().collect::<Vec<_>>().into_iter().rev() {
+                        pending.push(Item::Text(" "));
+                        pending.push(Item::Number(r, depth + 1));
+                    }
+                    pending.push(Item::Text("| "));
+                    for l in x.iter_left().collect::<Vec<_>>().into_iter().rev() {
+                        pending.push(Item::Text(" "));
+                        pending.push(Item::Number(l, depth + 1));
+                    }
+                    pending.push(Item::Text("{ "));
+                }
+            }
+        }
+
+        s
+    }
+
+    /// Wraps this number as a `SurrealInfinite` whose sets hold exactly this number's options.
+    pub fn to_infinite(&self) -> SurrealInfinite {
+        SurrealInfinite::from_finite(*self)
+    }
+
+    /// Wraps this number as a `SurrealElement` so it can sit in the sets of an infinite number.
+    pub fn to_element(&self) -> SurrealElement {
+        // lazy?
+        SurrealElement::Finite(*self)
+    }
 
-    // get birthday of surreal
     // create pseudo surreals? (would break Eq and Ord laws if included in SurrealFinite)
 }
 
@@ -132,25 +840,110 @@ impl Eq for SurrealFinite {}
 
 impl PartialOrd for SurrealFinite {
     fn partial_cmp(&self, other: &SurrealFinite) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SurrealFinite {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // floats are far cheaper to compare, and exact ones order the numbers just as leq would
+        if let (Some(a), Some(b)) = (
+            construction::stof_exact(self),
+            construction::stof_exact(other),
+        ) {
+            return a.partial_cmp(&b).unwrap(); // exact values are finite
+        }
+
         if !construction::leq(self, other) {
-            Some(Ordering::Greater)
+            Ordering::Greater
         } else if !construction::leq(other, self) {
-            Some(Ordering::Less)
+            Ordering::Less
         } else {
-            Some(Ordering::Equal)
+            Ordering::Equal
         }
     }
 }
 
-impl Ord for SurrealFinite {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+/// Compares the number with a float through `stof`, so this is only as precise as an `f64`, unlike comparison between surreal numbers.
+impl PartialEq<f64> for SurrealFinite {
+    fn eq(&self, other: &f64) -> bool {
+        self.stof() == *other
+    }
+}
+
+impl PartialEq<SurrealFinite> for f64 {
+    fn eq(&self, other: &SurrealFinite) -> bool {
+        *self == other.stof()
+    }
+}
+
+/// Orders the number against a float through `stof`, so this is only as precise as an `f64`.
+impl PartialOrd<f64> for SurrealFinite {
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+        self.stof().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<SurrealFinite> for f64 {
+    fn partial_cmp(&self, other: &SurrealFinite) -> Option<Ordering> {
+        self.partial_cmp(&other.stof())
     }
 }
 
 impl Hash for SurrealFinite {
+    // hashes the slot of the simplest form, so that numbers equal in value hash equally
     fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write_u64(self.hash);
+        state.write_u64(self.canonicalize().hash);
+    }
+}
+
+impl Default for SurrealFinite {
+    fn default() -> SurrealFinite {
+        SurrealFinite::zero()
+    }
+}
+
+macro_rules! impl_from_int {
+    (signed $($t:ty)*) => {$(
+        /// Builds the integer exactly, in a number of steps equal to its magnitude (its birthday).
+        ///
+        /// Panics if the magnitude is over `2^24`, as the number would be too deep to build in practice; with the `num-traits` feature, `FromPrimitive::from_i64` returns `None` instead.
+        impl From<$t> for SurrealFinite {
+            fn from(n: $t) -> SurrealFinite {
+                // the magnitude is taken as unsigned so that negating the minimum value can't overflow
+                construction::checked_integer((n as i64).unsigned_abs(), n < 0)
+                    .unwrap_or_else(|| construction::too_large(n))
+            }
+        }
+    )*};
+    (unsigned $($t:ty)*) => {$(
+        /// Builds the integer exactly, in a number of steps equal to its magnitude (its birthday).
+        ///
+        /// Panics if it is over `2^24`, as the number would be too deep to build in practice; with the `num-traits` feature, `FromPrimitive::from_u64` returns `None` instead.
+        impl From<$t> for SurrealFinite {
+            fn from(n: $t) -> SurrealFinite {
+                construction::checked_integer(n as u64, false).unwrap_or_else(|| construction::too_large(n))
+            }
+        }
+    )*};
+}
+
+impl_from_int! { signed i8 i16 i32 i64 }
+impl_from_int! { unsigned u8 u16 u32 u64 }
+
+/// Converts a floating-point number that is a short dyadic fraction, reporting any other float instead of panicking like [`ftos`].
+impl TryFrom<f64> for SurrealFinite {
+    type Error = TryFromFloatError;
+
+    fn try_from(f: f64) -> Result<SurrealFinite, TryFromFloatError> {
+        if !f.is_finite() {
+            return Err(TryFromFloatError::NotFinite);
+        }
+        if !construction::is_convertible(f) {
+            return Err(TryFromFloatError::TooLarge); // the search would have to build every integer up to it
+        }
+
+        construction::float_search(f, 0.0, MAX_FLOAT_BITS).ok_or(TryFromFloatError::NotDyadic)
     }
 }
 
@@ -204,13 +997,127 @@ impl ops::MulAssign for SurrealFinite {
     }
 }
 
+/// Multiplies by `2^n`, by shifting the exact value of the number rather than through general multiplication.
+///
+/// Panics if the result is over `2^24` in magnitude, like `integer`, as its simplest form would be too deep to build.
+impl ops::Shl<u32> for SurrealFinite {
+    type Output = SurrealFinite;
+
+    fn shl(self, n: u32) -> SurrealFinite {
+        let (numerator, denominator) = self.dyadic_parts();
+        let exponent = denominator.trailing_zeros();
+        if numerator == 0 {
+            return SurrealFinite::zero();
+        }
+
+        // checked before building, as dyadic steps through every integer on the way to the result
+        let magnitude = u128::from(numerator.unsigned_abs());
+        let limit = u128::from(construction::MAX_MAGNITUDE);
+        let over = if n <= exponent {
+            magnitude > limit << (exponent - n)
+        } else {
+            n - exponent >= 64 || magnitude << (n - exponent) > limit
+        };
+        if over {
+            construction::too_large(format_args!("{} * 2^{}", self.stof(), n));
+        }
+
+        if n <= exponent {
+            construction::dyadic(numerator, exponent - n)
+        } else {
+            construction::dyadic(numerator << (n - exponent), 0) // at most 2^24, so it fits
+        }
+    }
+}
+
+impl ops::ShlAssign<u32> for SurrealFinite {
+    fn shl_assign(&mut self, n: u32) {
+        self.hash = (*self << n).hash;
+    }
+}
+
+/// Divides by `2^n`, by shifting the exact value of the number rather than through general division.
+///
+/// Panics if the result's denominator doesn't fit in 64 bits.
+impl ops::Shr<u32> for SurrealFinite {
+    type Output = SurrealFinite;
+
+    fn shr(self, n: u32) -> SurrealFinite {
+        let (numerator, denominator) = self.dyadic_parts();
+        let exponent = denominator.trailing_zeros() + n;
+        assert!(
+            numerator == 0 || exponent < 64,
+            "attempt to shift right with overflow"
+        );
+        construction::dyadic(numerator, exponent)
+    }
+}
+
+impl ops::ShrAssign<u32> for SurrealFinite {
+    fn shr_assign(&mut self, n: u32) {
+        self.hash = (*self >> n).hash;
+    }
+}
+
+impl iter::Sum for SurrealFinite {
+    fn sum<I: Iterator<Item = SurrealFinite>>(iter: I) -> SurrealFinite {
+        iter.fold(SurrealFinite::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a> iter::Sum<&'a SurrealFinite> for SurrealFinite {
+    fn sum<I: Iterator<Item = &'a SurrealFinite>>(iter: I) -> SurrealFinite {
+        iter.copied().sum()
+    }
+}
+
+impl iter::Product for SurrealFinite {
+    fn product<I: Iterator<Item = SurrealFinite>>(iter: I) -> SurrealFinite {
+        iter.fold(SurrealFinite::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a> iter::Product<&'a SurrealFinite> for SurrealFinite {
+    fn product<I: Iterator<Item = &'a SurrealFinite>>(iter: I) -> SurrealFinite {
+        iter.copied().product()
+    }
+}
+
+impl ops::Div<SurrealFinite> for SurrealFinite {
+    type Output = SurrealFinite;
+
+    /// Panics if `other` is zero or the quotient is not a dyadic rational; use [`div`] to handle these cases.
+    fn div(self, other: SurrealFinite) -> SurrealFinite {
+        arithmetic::div(self, other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl ops::DivAssign for SurrealFinite {
+    fn div_assign(&mut self, other: SurrealFinite) {
+        self.hash = (*self / other).hash;
+    }
+}
+
 impl ops::Rem<SurrealFinite> for SurrealFinite {
     type Output = SurrealFinite;
 
+    /// Returns the remainder in `[0, |other|)`, whatever the signs of `self` and `other`.
+    ///
+    /// Panics if `other` is zero.
     fn rem(self, other: SurrealFinite) -> SurrealFinite {
+        let zero = SurrealFinite::zero();
+        assert!(
+            other != zero,
+            "attempt to calculate the remainder with a divisor of zero"
+        );
+        let modulus = if other < zero { -other } else { other };
+
         let mut total = self;
-        while total >= other {
-            total -= other;
+        while total >= modulus {
+            total -= modulus;
+        }
+        while total < zero {
+            total += modulus;
         }
         total
     }
@@ -218,26 +1125,86 @@ impl ops::Rem<SurrealFinite> for SurrealFinite {
 
 impl ops::RemAssign for SurrealFinite {
     fn rem_assign(&mut self, other: SurrealFinite) {
-        while *self >= other {
-            self.hash = (*self - other).hash;
+        self.hash = (*self % other).hash;
+    }
+}
+
+const DEBUG_DEPTH: usize = 3;
+
+/// Shows the value and the structure of the number, with options nested more than a few levels deep elided, alongside its hash.
+///
+/// # Examples
+///
+/// ```
+/// let half = surreal::ftos(0.5);
+/// assert!(format!("{:?}", half).starts_with("SurrealFinite(0.5 = { { | } | { { | } | } }, hash: 0x"));
+/// ```
+impl fmt::Debug for SurrealFinite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !construction::is_cached(self.hash) {
+            // debugging shouldn't panic, even though using the number would
+            return write!(f, "SurrealFinite(cleared, hash: {:#018x})", self.hash);
         }
+
+        write!(
+            f,
+            "SurrealFinite({} = {}, hash: {:#018x})",
+            self.stof(),
+            self.to_structure_string(Some(DEBUG_DEPTH)),
+            self.hash
+        )
     }
 }
 
+/// Writes the `< L | R >` form, with each option shown as a float, or as an exact fraction `n/d` with the `{:#}` flag.
+///
+/// # Examples
+///
+/// ```
+/// let x = surreal::ftos(0.375);
+/// assert!(x.to_string() == "< 0.25 | 0.5 >");
+/// assert!(format!("{:#}", x) == "< 1/4 | 1/2 >");
+/// ```
 impl fmt::Display for SurrealFinite {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let exact = f.alternate();
+        let write_set = |f: &mut fmt::Formatter, set: Vec<SurrealFinite>| {
+            for s in set {
+                if !exact {
+                    write!(f, "{} ", s.stof()).unwrap();
+                } else {
+                    match s.dyadic_parts() {
+                        (n, 1) => write!(f, "{} ", n).unwrap(),
+                        (n, d) => write!(f, "{}/{} ", n, d).unwrap(),
+                    }
+                }
+            }
+        };
+
         write!(f, "< ").unwrap();
-        for s in self.iter_left() {
-            write!(f, "{} ", s.stof()).unwrap();
-        }
+        write_set(f, self.iter_left().collect());
 
         write!(f, "| ").unwrap();
 
-        for s in self.iter_right() {
-            write!(f, "{} ", s.stof()).unwrap();
-        }
+        write_set(f, self.iter_right().collect());
         write!(f, ">").unwrap();
 
         Ok(())
     }
 }
+
+/// Parses the `< L | R >` form produced by `Display`, where each set holds floating-point numbers (converted with [`ftos`]), dyadic fractions like `3/8`, or nested surreal numbers.
+///
+/// # Examples
+///
+/// ```
+/// let one: surreal::SurrealFinite = "< 0 | >".parse().unwrap();
+/// assert!(one == surreal::SurrealFinite::one());
+/// ```
+impl FromStr for SurrealFinite {
+    type Err = ParseSurrealError;
+
+    fn from_str(s: &str) -> Result<SurrealFinite, ParseSurrealError> {
+        parse::parse(s)
+    }
+}
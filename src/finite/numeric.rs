@@ -0,0 +1,110 @@
+use core::convert::TryFrom;
+
+use num_traits::{FromPrimitive, Inv, Num, One, Signed, ToPrimitive, Zero};
+
+use super::{construction, ParseSurrealError, SurrealFinite};
+
+impl Zero for SurrealFinite {
+    fn zero() -> SurrealFinite {
+        SurrealFinite::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == SurrealFinite::zero()
+    }
+}
+
+impl One for SurrealFinite {
+    fn one() -> SurrealFinite {
+        SurrealFinite::one()
+    }
+}
+
+impl Num for SurrealFinite {
+    type FromStrRadixErr = ParseSurrealError;
+
+    // reads a plain literal like "-1.01" in the given radix, which must name a dyadic fraction
+    fn from_str_radix(s: &str, radix: u32) -> Result<SurrealFinite, ParseSurrealError> {
+        let f = f64::from_str_radix(s, radix).map_err(|_| ParseSurrealError::Syntax)?;
+        SurrealFinite::try_from(f).map_err(|_| ParseSurrealError::Syntax)
+    }
+}
+
+impl Signed for SurrealFinite {
+    fn abs(&self) -> SurrealFinite {
+        if *self < SurrealFinite::zero() {
+            -*self
+        } else {
+            *self
+        }
+    }
+
+    fn abs_sub(&self, other: &SurrealFinite) -> SurrealFinite {
+        if *self <= *other {
+            SurrealFinite::zero()
+        } else {
+            *self - *other
+        }
+    }
+
+    fn signum(&self) -> SurrealFinite {
+        let zero = SurrealFinite::zero();
+        if *self < zero {
+            -SurrealFinite::one()
+        } else if *self > zero {
+            SurrealFinite::one()
+        } else {
+            zero
+        }
+    }
+
+    fn is_positive(&self) -> bool {
+        *self > SurrealFinite::zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        *self < SurrealFinite::zero()
+    }
+}
+
+impl ToPrimitive for SurrealFinite {
+    // only integers convert, exactly, rather than being truncated towards zero
+    fn to_i64(&self) -> Option<i64> {
+        if self.is_integer() {
+            construction::checked_dyadic_parts(self).map(|(n, _)| n)
+        } else {
+            None
+        }
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.to_i64().and_then(|n| u64::try_from(n).ok())
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.stof())
+    }
+}
+
+impl FromPrimitive for SurrealFinite {
+    // integers too large to build in practice are refused, where From would panic
+    fn from_i64(n: i64) -> Option<SurrealFinite> {
+        construction::checked_integer(n.unsigned_abs(), n < 0)
+    }
+
+    fn from_u64(n: u64) -> Option<SurrealFinite> {
+        construction::checked_integer(n, false)
+    }
+
+    fn from_f64(f: f64) -> Option<SurrealFinite> {
+        SurrealFinite::try_from(f).ok()
+    }
+}
+
+impl Inv for SurrealFinite {
+    type Output = Option<SurrealFinite>; // most reciprocals aren't dyadic
+
+    fn inv(self) -> Option<SurrealFinite> {
+        self.recip()
+    }
+}
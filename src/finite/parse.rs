@@ -0,0 +1,155 @@
+use alloc::vec::{IntoIter, Vec};
+use core::error::Error;
+use core::fmt;
+use core::iter::Peekable;
+
+use super::construction::is_convertible;
+use super::{ftos, SurrealFinite, TryNewError};
+
+/// An error produced when parsing a surreal number from a string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseSurrealError {
+    /// The string was not of the form `< L | R >`, where each set holds finite numbers, dyadic fractions or nested surreal numbers, nor, for a `SurrealInfinite`, a known name or a single number.
+    Syntax,
+    /// Some number in a left set was not less than every number in the matching right set.
+    NotANumber,
+    /// A number was too large for `ftos` to build, being over `2^24` in magnitude.
+    OutOfRange,
+    /// A pair of sets held more options than [`set_max_options`](crate::set_max_options) allows.
+    TooManyOptions,
+}
+
+impl fmt::Display for ParseSurrealError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseSurrealError::Syntax => write!(f, "invalid surreal number syntax"),
+            ParseSurrealError::NotANumber => write!(f, "left set is not less than right set"),
+            ParseSurrealError::OutOfRange => write!(f, "number is too large to build"),
+            ParseSurrealError::TooManyOptions => write!(f, "sets hold too many options"),
+        }
+    }
+}
+
+impl Error for ParseSurrealError {}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Open,
+    Bar,
+    Close,
+    Number(&'a str),
+}
+
+type Tokens<'a> = Peekable<IntoIter<Token<'a>>>;
+
+fn tokenize(s: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (i, c) in s.char_indices() {
+        if c == '<' || c == '|' || c == '>' || c.is_whitespace() {
+            if let Some(j) = start.take() {
+                tokens.push(Token::Number(&s[j..i]));
+            }
+            match c {
+                '<' => tokens.push(Token::Open),
+                '|' => tokens.push(Token::Bar),
+                '>' => tokens.push(Token::Close),
+                _ => {}
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(j) = start {
+        tokens.push(Token::Number(&s[j..]));
+    }
+
+    tokens
+}
+
+fn expect(tokens: &mut Tokens, token: Token) -> Result<(), ParseSurrealError> {
+    if tokens.next() == Some(token) {
+        Ok(())
+    } else {
+        Err(ParseSurrealError::Syntax)
+    }
+}
+
+fn parse_number(n: &str) -> Result<SurrealFinite, ParseSurrealError> {
+    let f: f64 = match n.find('/') {
+        Some(i) => {
+            let numerator: i64 = n[..i].parse().map_err(|_| ParseSurrealError::Syntax)?;
+            let denominator: u64 = n[i + 1..].parse().map_err(|_| ParseSurrealError::Syntax)?;
+            if !denominator.is_power_of_two() {
+                return Err(ParseSurrealError::Syntax); // not dyadic, so it would only be approximated
+            }
+            numerator as f64 / denominator as f64
+        }
+        None => n.parse().map_err(|_| ParseSurrealError::Syntax)?,
+    };
+
+    if !f.is_finite() {
+        return Err(ParseSurrealError::Syntax); // ftos would panic
+    }
+    if !is_convertible(f) {
+        return Err(ParseSurrealError::OutOfRange);
+    }
+    Ok(ftos(f))
+}
+
+fn parse_set(tokens: &mut Tokens) -> Result<Vec<SurrealFinite>, ParseSurrealError> {
+    let mut set = Vec::new();
+
+    loop {
+        match tokens.peek().cloned() {
+            Some(Token::Open) => set.push(parse_surreal(tokens)?),
+            Some(Token::Number(n)) => {
+                set.push(parse_number(n)?);
+                tokens.next();
+            }
+            _ => return Ok(set),
+        }
+    }
+}
+
+fn parse_surreal(tokens: &mut Tokens) -> Result<SurrealFinite, ParseSurrealError> {
+    expect(tokens, Token::Open)?;
+    let left = parse_set(tokens)?;
+    expect(tokens, Token::Bar)?;
+    let right = parse_set(tokens)?;
+    expect(tokens, Token::Close)?;
+
+    SurrealFinite::try_new(left, right).map_err(|error| match error {
+        TryNewError::NotANumber(_) => ParseSurrealError::NotANumber,
+        TryNewError::TooManyOptions { .. } => ParseSurrealError::TooManyOptions,
+    })
+}
+
+/// Parses a surreal number written in the form produced by its `Display` implementation.
+pub fn parse(s: &str) -> Result<SurrealFinite, ParseSurrealError> {
+    let mut tokens = tokenize(s).into_iter().peekable();
+    let x = parse_surreal(&mut tokens)?;
+
+    if tokens.next().is_some() {
+        return Err(ParseSurrealError::Syntax);
+    }
+    Ok(x)
+}
+
+/// Parses a single number as it would appear in a set: a float, a dyadic fraction, or a surreal number in the form produced by `Display`.
+pub fn parse_option(s: &str) -> Result<SurrealFinite, ParseSurrealError> {
+    let mut tokens = tokenize(s).into_iter().peekable();
+    let x = match tokens.peek().cloned() {
+        Some(Token::Number(n)) => {
+            tokens.next();
+            parse_number(n)?
+        }
+        _ => parse_surreal(&mut tokens)?,
+    };
+
+    if tokens.next().is_some() {
+        return Err(ParseSurrealError::Syntax);
+    }
+    Ok(x)
+}
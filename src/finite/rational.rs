@@ -0,0 +1,64 @@
+use core::convert::TryFrom;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+use super::{construction, SurrealFinite};
+
+impl SurrealFinite {
+    /// Returns the exact value of this number as a fraction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate num_rational;
+    /// use num_rational::BigRational;
+    ///
+    /// let r = surreal::ftos(0.375).to_big_rational();
+    /// assert!(r == BigRational::new(3.into(), 8.into()));
+    /// ```
+    pub fn to_big_rational(&self) -> BigRational {
+        // the first run of equal signs counts whole steps, and each sign after it moves half as far as the last
+        let signs = self.sign_expansion();
+        let run = signs
+            .iter()
+            .take_while(|&&s| Some(&s) == signs.first())
+            .count();
+
+        let mut value = BigRational::from_integer(BigInt::from(run));
+        if signs.first() == Some(&false) {
+            value = -value;
+        }
+
+        let mut step = BigRational::new(BigInt::from(1), BigInt::from(2));
+        for &sign in &signs[run..] {
+            if sign {
+                value += &step;
+            } else {
+                value -= &step;
+            }
+            step /= BigInt::from(2);
+        }
+
+        value
+    }
+
+    /// Builds the number equal to `r`, or returns `None` if its denominator isn't a power of two or its numerator doesn't fit in an `i64`.
+    ///
+    /// Also returns `None` if `r` is over `2^24` in magnitude or its denominator is over `2^126`, as the number would be too deep to build.
+    pub fn from_big_rational(r: &BigRational) -> Option<SurrealFinite> {
+        let denominator = r.denom(); // already in lowest terms, and positive
+        let exponent = denominator.trailing_zeros()?;
+        if denominator.bits() != exponent + 1 || exponent > 126 {
+            return None;
+        }
+
+        let numerator = i64::try_from(r.numer()).ok()?;
+        // past 2^-40, a numerator that fits in an i64 is always under 2^24 in value
+        let limit = u128::from(construction::MAX_MAGNITUDE);
+        if exponent < 40 && u128::from(numerator.unsigned_abs()) > limit << exponent {
+            return None;
+        }
+        Some(construction::dyadic(numerator, exponent as u32))
+    }
+}
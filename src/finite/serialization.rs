@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "std")]
+use serde_json;
+
+#[cfg(feature = "std")]
+use super::construction;
+use super::SurrealFinite;
+
+// the hash is only meaningful within the current process, so numbers are written out as their full structure instead
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "SurrealFinite")]
+struct Sets {
+    left: Vec<SurrealFinite>,
+    right: Vec<SurrealFinite>,
+}
+
+impl Serialize for SurrealFinite {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Sets {
+            left: self.iter_left().collect(),
+            right: self.iter_right().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SurrealFinite {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<SurrealFinite, D::Error> {
+        let sets = Sets::deserialize(deserializer)?;
+        Ok(SurrealFinite::new_unchecked(sets.left, sets.right))
+    }
+}
+
+#[cfg(feature = "std")]
+const DUMP_VERSION: u32 = 1;
+
+// the whole cache is written with each structure once, children before parents, so its sets are indices rather than nested structures
+#[cfg(feature = "std")]
+#[derive(Serialize, Deserialize)]
+struct CacheDump {
+    version: u32,
+    structures: Vec<(Vec<u32>, Vec<u32>)>,
+}
+
+/// Writes every cached structure to `w` as JSON.
+#[cfg(feature = "std")]
+pub fn dump<W: Write>(w: W) -> io::Result<()> {
+    let dump = CacheDump {
+        version: DUMP_VERSION,
+        structures: construction::structures(), // the cache is unlocked again before writing
+    };
+    let mut w = BufWriter::new(w);
+    serde_json::to_writer(&mut w, &dump)?;
+    w.flush()
+}
+
+/// Reads structures written by `dump`, interning each one as if it had been built again.
+#[cfg(feature = "std")]
+pub fn load<R: Read>(r: R) -> io::Result<()> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let dump: CacheDump = serde_json::from_reader(BufReader::new(r))?;
+    if dump.version != DUMP_VERSION {
+        return Err(invalid("unsupported surreal cache dump version"));
+    }
+    if !construction::load_structures(&dump.structures) {
+        return Err(invalid("structure refers to a later one"));
+    }
+    Ok(())
+}
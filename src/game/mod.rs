@@ -0,0 +1,225 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops;
+
+use super::finite::SurrealFinite;
+
+/// The result of comparing two numbers or games, which unlike numbers may be incomparable.
+///
+/// Both `SurrealFinite::compare` and `SurrealGame::compare` return this, so code can compare either the same way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SurrealOrdering {
+    Less,
+    Equal,
+    Greater,
+    /// Neither game is at most the other, as with `*` and zero. Numbers are never confused.
+    Confused,
+}
+
+/// The name `SurrealGame::cmp_game` gives its result.
+pub type GameOrdering = SurrealOrdering;
+
+impl From<Ordering> for SurrealOrdering {
+    fn from(o: Ordering) -> SurrealOrdering {
+        match o {
+            Ordering::Less => SurrealOrdering::Less,
+            Ordering::Equal => SurrealOrdering::Equal,
+            Ordering::Greater => SurrealOrdering::Greater,
+        }
+    }
+}
+
+/// A combinatorial game, built like a surreal number but without requiring every left option to be less than every right option.
+///
+/// Such games include numbers, but also pseudo-numbers like `* = { 0 | 0 }`, which can't be `SurrealFinite` values without breaking `Eq` and `Ord`.
+#[derive(Clone, Debug)]
+pub struct SurrealGame {
+    left: Vec<SurrealGame>,
+    right: Vec<SurrealGame>,
+}
+
+impl SurrealGame {
+    /// Builds the game with the given options, which may be any games at all.
+    pub fn new(left: Vec<SurrealGame>, right: Vec<SurrealGame>) -> SurrealGame {
+        SurrealGame { left, right }
+    }
+
+    /// Returns the game with no options, in which the player to move loses.
+    pub fn zero() -> SurrealGame {
+        SurrealGame::new(vec![], vec![])
+    }
+
+    /// Returns `* = { 0 | 0 }`, in which the player to move wins; it's confused with zero.
+    pub fn star() -> SurrealGame {
+        SurrealGame::new(vec![SurrealGame::zero()], vec![SurrealGame::zero()])
+    }
+
+    /// Returns `↑ = { 0 | * }`, which is positive but less than every positive number.
+    pub fn up() -> SurrealGame {
+        SurrealGame::new(vec![SurrealGame::zero()], vec![SurrealGame::star()])
+    }
+
+    /// Returns `↓ = { * | 0 }`, the negative of `up`.
+    pub fn down() -> SurrealGame {
+        SurrealGame::new(vec![SurrealGame::star()], vec![SurrealGame::zero()])
+    }
+
+    pub fn iter_left(&self) -> impl Iterator<Item = &SurrealGame> {
+        self.left.iter()
+    }
+
+    pub fn iter_right(&self) -> impl Iterator<Item = &SurrealGame> {
+        self.right.iter()
+    }
+
+    /// Compares two games, returning `Confused` if neither is at most the other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use surreal::{GameOrdering, SurrealGame};
+    ///
+    /// let zero = SurrealGame::zero();
+    /// assert!(SurrealGame::star().cmp_game(&zero) == GameOrdering::Confused);
+    /// assert!(SurrealGame::up().cmp_game(&zero) == GameOrdering::Greater);
+    /// ```
+    pub fn cmp_game(&self, other: &SurrealGame) -> GameOrdering {
+        match (leq(self, other), leq(other, self)) {
+            (true, true) => GameOrdering::Equal,
+            (true, false) => GameOrdering::Less,
+            (false, true) => GameOrdering::Greater,
+            (false, false) => GameOrdering::Confused,
+        }
+    }
+
+    /// The same as `cmp_game`, under the name shared with `SurrealFinite::compare`.
+    pub fn compare(&self, other: &SurrealGame) -> SurrealOrdering {
+        self.cmp_game(other)
+    }
+
+    /// Returns the nim-value of an impartial game, the `n` for which it equals the nimber `*n`, or `None` if the two players' options differ.
+    ///
+    /// The options of both players must have the same set of nim-values, and the game's is the least one missing from that set. Partizan games are only recognised this way when each of their options already is, so a game equal to a nimber through dominated or reversible options still gives `None`.
+    pub fn nim_value(&self) -> Option<u64> {
+        let values = |options: &[SurrealGame]| -> Option<Vec<u64>> {
+            let mut v = options
+                .iter()
+                .map(|g| g.nim_value())
+                .collect::<Option<Vec<_>>>()?;
+            v.sort_unstable();
+            v.dedup();
+            Some(v)
+        };
+
+        let left = values(&self.left)?;
+        if left != values(&self.right)? {
+            return None;
+        }
+
+        // the minimum excludant, the first gap in the sorted values
+        Some(
+            (0..)
+                .zip(&left)
+                .find(|&(i, &v)| i != v)
+                .map_or(left.len() as u64, |(i, _)| i),
+        )
+    }
+
+    /// Converts the game to a number, or returns `None` if it or any of its options, however deeply nested, isn't in the form of a number.
+    pub fn try_into_number(&self) -> Option<SurrealFinite> {
+        let left = self
+            .left
+            .iter()
+            .map(|g| g.try_into_number())
+            .collect::<Option<Vec<_>>>()?;
+        let right = self
+            .right
+            .iter()
+            .map(|g| g.try_into_number())
+            .collect::<Option<Vec<_>>>()?;
+        SurrealFinite::new(left, right)
+    }
+}
+
+// the same definition as for numbers, which makes sense for any games
+fn leq(x: &SurrealGame, y: &SurrealGame) -> bool {
+    !x.left.iter().any(|xl| leq(y, xl)) && !y.right.iter().any(|yr| leq(yr, x))
+}
+
+impl From<SurrealFinite> for SurrealGame {
+    fn from(x: SurrealFinite) -> SurrealGame {
+        SurrealGame::new(
+            x.iter_left().map(SurrealGame::from).collect(),
+            x.iter_right().map(SurrealGame::from).collect(),
+        )
+    }
+}
+
+/// Games are equal when each is at most the other, so equal games may have different options.
+impl PartialEq for SurrealGame {
+    fn eq(&self, other: &SurrealGame) -> bool {
+        self.cmp_game(other) == GameOrdering::Equal
+    }
+}
+
+impl ops::Add<SurrealGame> for SurrealGame {
+    type Output = SurrealGame;
+
+    // the sum of two games is played by moving in either one of them
+    fn add(self, other: SurrealGame) -> SurrealGame {
+        let mut left: Vec<SurrealGame> = self
+            .left
+            .iter()
+            .map(|xl| xl.clone() + other.clone())
+            .collect();
+        left.extend(other.left.iter().map(|yl| self.clone() + yl.clone()));
+
+        let mut right: Vec<SurrealGame> = self
+            .right
+            .iter()
+            .map(|xr| xr.clone() + other.clone())
+            .collect();
+        right.extend(other.right.iter().map(|yr| self.clone() + yr.clone()));
+
+        SurrealGame::new(left, right)
+    }
+}
+
+impl ops::Neg for SurrealGame {
+    type Output = SurrealGame;
+
+    fn neg(self) -> SurrealGame {
+        SurrealGame::new(
+            self.right.into_iter().map(|x| -x).collect(),
+            self.left.into_iter().map(|x| -x).collect(),
+        )
+    }
+}
+
+impl ops::Sub<SurrealGame> for SurrealGame {
+    type Output = SurrealGame;
+
+    fn sub(self, other: SurrealGame) -> SurrealGame {
+        self + (-other)
+    }
+}
+
+impl fmt::Display for SurrealGame {
+    // options that are numbers are printed as their values
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(x) = self.try_into_number() {
+            return write!(f, "{}", x.stof());
+        }
+
+        write!(f, "{{ ")?;
+        for x in &self.left {
+            write!(f, "{} ", x)?;
+        }
+        write!(f, "| ")?;
+        for x in &self.right {
+            write!(f, "{} ", x)?;
+        }
+        write!(f, "}}")
+    }
+}
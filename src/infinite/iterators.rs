@@ -0,0 +1,505 @@
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops;
+
+use super::{SurrealFinite, SurrealInfinite};
+
+/// Either a finite or an infinite surreal number.
+#[derive(Clone)]
+pub enum SurrealElement {
+    Finite(SurrealFinite),
+    Infinite(SurrealInfinite),
+}
+
+impl SurrealElement {
+    /// Returns the finite number held by this element, or `None` if it is infinite.
+    pub fn as_finite(&self) -> Option<SurrealFinite> {
+        match self {
+            Self::Finite(s) => Some(*s),
+            Self::Infinite(_) => None,
+        }
+    }
+
+    #[deprecated(note = "use `as_finite`, which returns `None` instead of panicking")]
+    pub fn coerce_finite(&self) -> SurrealFinite {
+        self.as_finite().unwrap()
+    }
+}
+
+/// The number of options infinite elements are truncated to when compared.
+const COMPARE_PRECISION: usize = 16;
+
+impl SurrealElement {
+    fn approximate(&self) -> Option<SurrealFinite> {
+        match self {
+            Self::Finite(s) => Some(*s),
+            Self::Infinite(s) => s.to_finite(COMPARE_PRECISION),
+        }
+    }
+}
+
+/// Finite elements are compared exactly. Infinite elements are first truncated to their first 16 options, so, like `SurrealInfinite::approx_eq`, this is a heuristic.
+impl PartialEq for SurrealElement {
+    fn eq(&self, other: &SurrealElement) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+/// Finite elements are ordered exactly. Infinite elements are first truncated to their first 16 options, giving `None` if the truncation isn't a number.
+impl PartialOrd for SurrealElement {
+    fn partial_cmp(&self, other: &SurrealElement) -> Option<Ordering> {
+        Some(self.approximate()?.cmp(&other.approximate()?))
+    }
+}
+
+impl SurrealElement {
+    /// Formats the element, showing at most `depth` levels of nested sets.
+    pub(crate) fn format(&self, depth: usize) -> String {
+        match self {
+            Self::Finite(s) => s.stof().to_string(),
+            Self::Infinite(s) => s.format(depth, false),
+        }
+    }
+}
+
+impl fmt::Display for SurrealElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Finite(s) => write!(f, "{}", s.stof()),
+            Self::Infinite(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl ops::Add for SurrealElement {
+    type Output = SurrealElement;
+
+    /// Adds finite numbers, and infinite numbers with a known value, exactly; only sums involving a genuinely infinite number stay infinite.
+    fn add(self, other: SurrealElement) -> SurrealElement {
+        // adding zero is skipped, so that sums like ω - 1 keep ω itself among their options
+        if exact_value(&self) == Some(SurrealFinite::zero()) {
+            return other;
+        }
+        if exact_value(&other) == Some(SurrealFinite::zero()) {
+            return self;
+        }
+
+        match (self, other) {
+            (SurrealElement::Finite(x), SurrealElement::Finite(y)) => (x + y).to_element(),
+            (SurrealElement::Finite(x), SurrealElement::Infinite(y)) => match y.value {
+                Some(s) => (x + s).to_element(),
+                None => (x.to_infinite() + y).to_element(),
+            },
+            (SurrealElement::Infinite(x), SurrealElement::Finite(y)) => match x.value {
+                Some(s) => (s + y).to_element(),
+                None => (x + y.to_infinite()).to_element(),
+            },
+            (SurrealElement::Infinite(x), SurrealElement::Infinite(y)) => {
+                match (x.value, y.value) {
+                    (Some(a), Some(b)) => (a + b).to_element(),
+                    _ => (x + y).to_element(),
+                }
+            }
+        }
+    }
+}
+
+impl ops::Neg for SurrealElement {
+    type Output = SurrealElement;
+
+    fn neg(self) -> SurrealElement {
+        match self {
+            SurrealElement::Finite(s) => (-s).to_element(),
+            SurrealElement::Infinite(s) => (-s).to_element(),
+        }
+    }
+}
+
+impl ops::Sub for SurrealElement {
+    type Output = SurrealElement;
+
+    fn sub(self, other: SurrealElement) -> SurrealElement {
+        self + -other
+    }
+}
+
+impl ops::Mul for SurrealElement {
+    type Output = SurrealElement;
+
+    /// Multiplies like `Add` adds, exactly unless a genuinely infinite number is involved.
+    fn mul(self, other: SurrealElement) -> SurrealElement {
+        match (self, other) {
+            (SurrealElement::Finite(x), SurrealElement::Finite(y)) => (x * y).to_element(),
+            (SurrealElement::Finite(x), SurrealElement::Infinite(y)) => match y.value {
+                Some(s) => (x * s).to_element(),
+                None => (x.to_infinite() * y).to_element(),
+            },
+            (SurrealElement::Infinite(x), SurrealElement::Finite(y)) => match x.value {
+                Some(s) => (s * y).to_element(),
+                None => (x * y.to_infinite()).to_element(),
+            },
+            (SurrealElement::Infinite(x), SurrealElement::Infinite(y)) => (x * y).to_element(), // short-circuits when both are finite
+        }
+    }
+}
+
+pub(crate) fn exact_value(x: &SurrealElement) -> Option<SurrealFinite> {
+    match x {
+        SurrealElement::Finite(s) => Some(*s),
+        SurrealElement::Infinite(s) => s.value,
+    }
+}
+
+/// The elements taken from a set so far, so that taking a prefix again is free and taking a longer one only computes the new elements.
+struct TakeMemo {
+    taken: RefCell<Vec<SurrealElement>>,
+    requested: Cell<usize>, // if fewer elements than this were found, the set has ended
+}
+
+impl TakeMemo {
+    fn new() -> TakeMemo {
+        TakeMemo {
+            taken: RefCell::new(Vec::new()),
+            requested: Cell::new(0),
+        }
+    }
+
+    /// Returns whether a previous call found the end of the set.
+    fn ended(&self) -> bool {
+        self.taken.borrow().len() < self.requested.get()
+    }
+
+    /// Returns the first `n` elements, calling `more` with the elements found so far to compute any that are missing.
+    ///
+    /// Once `more` has returned fewer elements than asked for, the set is known to have ended, and `more` is never called again.
+    fn take<F>(&self, n: usize, more: F) -> Vec<SurrealElement>
+    where
+        F: FnOnce(&[SurrealElement], usize) -> Vec<SurrealElement>,
+    {
+        if n > self.requested.get() && !self.ended() {
+            let so_far = self.taken.borrow().clone(); // not borrowed during the computation, in case it takes from this set again
+            let new = more(&so_far, n);
+            self.taken.borrow_mut().extend(new);
+            self.requested.set(n);
+        }
+
+        self.taken.borrow().iter().take(n).cloned().collect()
+    }
+}
+
+pub trait SurrealIterator {
+    fn take(&self, n: usize) -> Vec<SurrealElement>;
+    /// Formats the first `n` elements, showing at most `depth` levels of sets nested inside them.
+    fn take_fmt(&self, n: usize, depth: usize) -> Vec<String>;
+}
+
+/// Formats the first few elements of the set, followed by `...` if there are more.
+pub(crate) fn format_set(set: &dyn SurrealIterator, depth: usize) -> String {
+    let mut s = String::new();
+    for (i, x) in set.take_fmt(6, depth).iter().enumerate() {
+        if i == 5 {
+            s.push_str("... ");
+        } else {
+            s.push_str(x);
+            s.push(' ');
+        }
+    }
+    s
+}
+
+pub struct SurrealBasicSet {
+    f: Rc<dyn Fn(Option<SurrealElement>, usize) -> Option<SurrealElement>>,
+    init: Option<SurrealElement>,
+    memo: TakeMemo,
+}
+
+impl SurrealBasicSet {
+    pub fn new(
+        f: Rc<dyn Fn(Option<SurrealElement>, usize) -> Option<SurrealElement>>, // should this only take just finite funcs or just infinite funcs?
+        init: Option<SurrealElement>,
+    ) -> SurrealBasicSet {
+        SurrealBasicSet {
+            f,
+            init,
+            memo: TakeMemo::new(),
+        }
+    }
+}
+
+impl SurrealIterator for SurrealBasicSet {
+    fn take(&self, n: usize) -> Vec<SurrealElement> {
+        self.memo.take(n, |so_far, n| {
+            let mut taken = Vec::new();
+            let mut prev = so_far.last().cloned().or_else(|| self.init.clone());
+
+            for i in so_far.len()..n {
+                let curr = (self.f)(prev.clone(), i);
+                match curr.clone() {
+                    Some(x) => taken.push(x),
+                    None => break,
+                }
+                prev = curr;
+            }
+
+            taken
+        })
+    }
+
+    fn take_fmt(&self, n: usize, depth: usize) -> Vec<String> {
+        self.take(n).iter().map(|x| x.format(depth)).collect()
+    }
+}
+
+pub struct SurrealZipSet {
+    iters: Vec<Rc<dyn SurrealIterator>>,
+    memo: TakeMemo,
+}
+
+impl SurrealZipSet {
+    pub fn new(iters: Vec<Rc<dyn SurrealIterator>>) -> SurrealZipSet {
+        SurrealZipSet {
+            iters,
+            memo: TakeMemo::new(),
+        }
+    }
+
+    pub fn new_rc(iters: Vec<Rc<dyn SurrealIterator>>) -> Rc<SurrealZipSet> {
+        // is new_rc ugly?
+        Rc::new(SurrealZipSet::new(iters))
+    }
+
+    fn zip(&self, n: usize) -> Vec<SurrealElement> {
+        // duplicates are dropped, so more may need to be taken from each set to find n distinct elements
+        let mut m = n;
+        loop {
+            let all_taken: Vec<Vec<SurrealElement>> =
+                self.iters.iter().map(|t| t.take(m)).collect();
+
+            let mut taken: Vec<SurrealElement> = Vec::new();
+            let mut seen: Vec<SurrealFinite> = Vec::new();
+            for i in 0..m {
+                for t in &all_taken {
+                    if i < t.len() {
+                        // only numbers with a known finite value can be compared exactly
+                        if let Some(v) = exact_value(&t[i]) {
+                            if seen.contains(&v) {
+                                continue;
+                            }
+                            seen.push(v);
+                        }
+                        taken.push(t[i].clone());
+                    }
+                }
+            }
+
+            if taken.len() >= n || all_taken.iter().all(|t| t.len() < m) {
+                taken.truncate(n);
+                return taken;
+            }
+            m *= 2;
+        }
+    }
+}
+
+impl SurrealIterator for SurrealZipSet {
+    fn take(&self, n: usize) -> Vec<SurrealElement> {
+        self.memo.take(n, |so_far, n| {
+            self.zip(n).into_iter().skip(so_far.len()).collect()
+        })
+    }
+
+    fn take_fmt(&self, n: usize, depth: usize) -> Vec<String> {
+        // formatted lazily, as the elements of sums and products may themselves be infinite
+        let all_taken: Vec<Vec<String>> = self.iters.iter().map(|t| t.take_fmt(n, depth)).collect();
+
+        let mut taken = Vec::new();
+        for i in 0..n {
+            for t in &all_taken {
+                if i < t.len() {
+                    taken.push(t[i].clone());
+                }
+            }
+        }
+
+        taken.truncate(n);
+        taken
+    }
+}
+
+pub struct SurrealAddSet {
+    lhs: SurrealElement,
+    rhs: Rc<dyn SurrealIterator>,
+    memo: TakeMemo,
+}
+
+impl SurrealAddSet {
+    pub fn new(lhs: SurrealElement, rhs: Rc<dyn SurrealIterator>) -> SurrealAddSet {
+        SurrealAddSet {
+            lhs,
+            rhs,
+            memo: TakeMemo::new(),
+        }
+    }
+
+    pub fn new_rc(lhs: SurrealElement, rhs: Rc<dyn SurrealIterator>) -> Rc<SurrealAddSet> {
+        Rc::new(SurrealAddSet::new(lhs, rhs))
+    }
+}
+
+impl SurrealIterator for SurrealAddSet {
+    fn take(&self, n: usize) -> Vec<SurrealElement> {
+        self.memo.take(n, |so_far, n| {
+            let mut taken = Vec::new();
+
+            for i in self.rhs.take(n).into_iter().skip(so_far.len()) {
+                taken.push(i + self.lhs.clone());
+            }
+
+            taken
+        })
+    }
+
+    fn take_fmt(&self, n: usize, depth: usize) -> Vec<String> {
+        let mut taken = Vec::new();
+
+        for i in self.rhs.take(n) {
+            // sums of exact values are printed as the value, and any other sum the way it was built
+            taken.push(match (exact_value(&i), exact_value(&self.lhs)) {
+                (Some(_), Some(_)) => (i + self.lhs.clone()).format(depth),
+                (Some(z), _) if z == SurrealFinite::zero() => self.lhs.format(depth),
+                (_, Some(z)) if z == SurrealFinite::zero() => i.format(depth),
+                _ => format!("({} + {})", i.format(depth), self.lhs.format(depth)),
+            });
+        }
+
+        taken
+    }
+}
+
+pub struct SurrealNegSet {
+    iter: Rc<dyn SurrealIterator>,
+    memo: TakeMemo,
+}
+
+impl SurrealNegSet {
+    pub fn new(iter: Rc<dyn SurrealIterator>) -> SurrealNegSet {
+        SurrealNegSet {
+            iter,
+            memo: TakeMemo::new(),
+        }
+    }
+
+    pub fn new_rc(iter: Rc<dyn SurrealIterator>) -> Rc<SurrealNegSet> {
+        Rc::new(SurrealNegSet::new(iter))
+    }
+}
+
+impl SurrealIterator for SurrealNegSet {
+    fn take(&self, n: usize) -> Vec<SurrealElement> {
+        self.memo.take(n, |so_far, n| {
+            let mut taken = Vec::new();
+
+            for i in self.iter.take(n).into_iter().skip(so_far.len()) {
+                taken.push(-i)
+            }
+
+            taken
+        })
+    }
+
+    fn take_fmt(&self, n: usize, depth: usize) -> Vec<String> {
+        let mut taken = Vec::new();
+
+        for i in self.iter.take(n) {
+            taken.push(format!("-{}", i.format(depth)))
+        }
+
+        taken
+    }
+}
+
+pub struct SurrealMulSet {
+    // the terms (a * y + x * b - a * b) of the product formula, for a in xs and b in ys
+    x: SurrealElement,
+    y: SurrealElement,
+    xs: Rc<dyn SurrealIterator>,
+    ys: Rc<dyn SurrealIterator>,
+    memo: TakeMemo,
+}
+
+impl SurrealMulSet {
+    pub fn new(
+        x: SurrealElement,
+        y: SurrealElement,
+        xs: Rc<dyn SurrealIterator>,
+        ys: Rc<dyn SurrealIterator>,
+    ) -> SurrealMulSet {
+        SurrealMulSet {
+            x,
+            y,
+            xs,
+            ys,
+            memo: TakeMemo::new(),
+        }
+    }
+
+    pub fn new_rc(
+        x: SurrealElement,
+        y: SurrealElement,
+        xs: Rc<dyn SurrealIterator>,
+        ys: Rc<dyn SurrealIterator>,
+    ) -> Rc<SurrealMulSet> {
+        Rc::new(SurrealMulSet::new(x, y, xs, ys))
+    }
+
+    fn pairs(&self, n: usize) -> Vec<(SurrealElement, SurrealElement)> {
+        // walks the diagonals of the grid of pairs, so that each option of x and y is reached eventually
+        let xs = self.xs.take(n);
+        let ys = self.ys.take(n);
+
+        let mut pairs = Vec::new();
+        for d in 0..(xs.len() + ys.len()) {
+            for i in 0..=d {
+                if pairs.len() == n {
+                    return pairs;
+                }
+                if i < xs.len() && d - i < ys.len() {
+                    pairs.push((xs[i].clone(), ys[d - i].clone()));
+                }
+            }
+        }
+
+        pairs
+    }
+}
+
+impl SurrealIterator for SurrealMulSet {
+    fn take(&self, n: usize) -> Vec<SurrealElement> {
+        self.memo.take(n, |so_far, n| {
+            let mut taken = Vec::new();
+
+            // the diagonals are walked in the same order however many pairs are taken
+            for (a, b) in self.pairs(n).into_iter().skip(so_far.len()) {
+                taken.push(a.clone() * self.y.clone() + self.x.clone() * b.clone() - a * b);
+            }
+
+            taken
+        })
+    }
+
+    fn take_fmt(&self, n: usize, depth: usize) -> Vec<String> {
+        let mut taken = Vec::new();
+
+        for (a, b) in self.pairs(n) {
+            let (a, b) = (a.format(depth), b.format(depth));
+            let (x, y) = (self.x.format(depth), self.y.format(depth));
+            taken.push(format!("({} * {} + {} * {} - {} * {})", a, y, x, b, a, b));
+        }
+
+        taken
+    }
+}
@@ -1,3 +1,491 @@
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops;
+use core::str::FromStr;
+
+pub(crate) mod iterators;
+
+pub use self::iterators::SurrealElement;
+use self::iterators::*;
+use super::finite::parse::parse_option;
+use super::finite::{ftos, ParseSurrealError, SurrealFinite};
+
+/// How well a truncation to finitely many options describes an infinite number, as returned by [`SurrealInfinite::classify`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Approximation {
+    /// Every set ended within the truncation, so this is the number itself.
+    Exact(SurrealFinite),
+    /// Some set was cut off, so this is only the number formed by the options seen so far.
+    Truncated(SurrealFinite),
+    /// The options seen so far don't form a number, so there is nothing to approximate by.
+    Diverges,
+}
+
+const DEFAULT_FMT_DEPTH: usize = 3;
+
+// how many options of each set definitely_not_eq looks through for one that separates the numbers
+const MAX_SEPARATING_OPTIONS: usize = 1 << 10;
+
+/// A representation of surreal numbers with potentially infinite sets.
+///
+/// Cloning is cheap: the clone shares the sets, and with them every option already computed for either copy.
+#[derive(Clone)]
 pub struct SurrealInfinite {
+    left: Rc<dyn SurrealIterator>,
+    right: Rc<dyn SurrealIterator>,
+    name: Option<String>,
+    value: Option<SurrealFinite>,
+    fmt_depth: usize,
+}
+
+impl SurrealInfinite {
+    pub fn new(
+        left: Rc<dyn Fn(Option<SurrealElement>, usize) -> Option<SurrealElement>>,
+        left_first: Option<SurrealElement>,
+        right: Rc<dyn Fn(Option<SurrealElement>, usize) -> Option<SurrealElement>>,
+        right_first: Option<SurrealElement>,
+        name: Option<String>,
+    ) -> SurrealInfinite {
+        SurrealInfinite {
+            left: Rc::new(SurrealBasicSet::new(left, left_first)),
+            right: Rc::new(SurrealBasicSet::new(right, right_first)),
+            name,
+            value: None,
+            fmt_depth: DEFAULT_FMT_DEPTH,
+        }
+    }
+
+    fn new_raw(left: Rc<dyn SurrealIterator>, right: Rc<dyn SurrealIterator>) -> SurrealInfinite {
+        SurrealInfinite {
+            left,
+            right,
+            name: None,
+            value: None,
+            fmt_depth: DEFAULT_FMT_DEPTH,
+        }
+    }
+
+    pub fn from_finite(x: SurrealFinite) -> SurrealInfinite {
+        let vec_left: Vec<SurrealFinite> = x.iter_left().collect();
+        let left = move |_, idx: usize| -> Option<SurrealElement> {
+            if idx < vec_left.len() {
+                Some(SurrealElement::Finite(vec_left[idx]))
+            } else {
+                None
+            }
+        };
+
+        let vec_right: Vec<SurrealFinite> = x.iter_right().collect();
+        let right = move |_, idx: usize| -> Option<SurrealElement> {
+            if idx < vec_right.len() {
+                Some(SurrealElement::Finite(vec_right[idx]))
+            } else {
+                None
+            }
+        };
+
+        SurrealInfinite {
+            left: Rc::new(SurrealBasicSet::new(Rc::new(left), None)),
+            right: Rc::new(SurrealBasicSet::new(Rc::new(right), None)),
+            name: None,
+            value: Some(x),
+            fmt_depth: DEFAULT_FMT_DEPTH,
+        }
+    }
+
+    pub fn omega() -> SurrealInfinite {
+        let left =
+            |_, idx: usize| -> Option<SurrealElement> { Some(ftos(idx as f64 + 1.0).to_element()) };
+
+        let right = |_, _| -> Option<SurrealElement> { None }; // helper function for empty closure
+
+        SurrealInfinite::new(
+            Rc::new(left),
+            None,
+            Rc::new(right),
+            None,
+            Some(String::from("ω")),
+        )
+    }
+
+    pub fn epsilon() -> SurrealInfinite {
+        let left = |_, idx: usize| -> Option<SurrealElement> {
+            if idx == 0 {
+                Some(SurrealFinite::zero().to_element())
+            } else {
+                None
+            }
+        }; // helper function for once closure
+
+        let right = |prev: Option<SurrealElement>, _| -> Option<SurrealElement> {
+            let prev = prev?.as_finite()?; // every element of the set is finite, so this only ends it if misused
+            Some(
+                SurrealFinite::new(vec![SurrealFinite::zero()], vec![prev])
+                    .unwrap()
+                    .to_element(),
+            )
+        };
+
+        SurrealInfinite::new(
+            Rc::new(left),
+            None,
+            Rc::new(right),
+            Some(SurrealFinite::one().to_element()),
+            Some(String::from("ϵ")),
+        )
+    }
+
+    /// Builds the finite number formed by the first `precision` options of each set, truncating nested numbers the same way, or `None` if they don't form a number.
+    ///
+    /// Whether anything was actually cut off isn't reported; `classify` tells the two apart.
+    pub fn to_finite(&self, precision: usize) -> Option<SurrealFinite> {
+        let recurse = |x: &SurrealElement| -> Option<SurrealFinite> {
+            match x {
+                SurrealElement::Finite(s) => Some(*s),
+                SurrealElement::Infinite(s) => s.to_finite(precision),
+            }
+        };
+
+        let left_trunc: Vec<Option<SurrealFinite>> =
+            self.left.take(precision).iter().map(recurse).collect();
+        let right_trunc: Vec<Option<SurrealFinite>> =
+            self.right.take(precision).iter().map(recurse).collect();
+
+        if left_trunc.contains(&None) || right_trunc.contains(&None) {
+            None // if pseudo was returned
+        } else {
+            SurrealFinite::new(
+                left_trunc.into_iter().flatten().collect(),
+                right_trunc.into_iter().flatten().collect(),
+            )
+        }
+    }
+
+    /// Yields `to_finite(1)`, `to_finite(2)` and so on, skipping truncations that aren't numbers, so that the approximations can be watched converging.
+    ///
+    /// The iterator never ends, and if no truncation is a number, never yields either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let omega: Vec<_> = surreal::SurrealInfinite::omega().approximations().take(3).collect();
+    /// assert!(omega == vec![surreal::ftos(2.0), surreal::ftos(3.0), surreal::ftos(4.0)]);
+    /// ```
+    pub fn approximations(&self) -> impl Iterator<Item = SurrealFinite> {
+        let x = self.clone();
+        (1..).filter_map(move |precision| x.to_finite(precision))
+    }
+
+    /// Truncates the number like `to_finite`, but also reports whether anything was cut off, so that a number which really is finite can be told from one that was only truncated, like `ω`.
+    pub fn classify(&self, precision: usize) -> Approximation {
+        if let Some(v) = self.value {
+            return Approximation::Exact(v);
+        }
+
+        let mut exact = true;
+        let mut truncate = |set: &Rc<dyn SurrealIterator>| -> Option<Vec<SurrealFinite>> {
+            let taken = set.take(precision + 1); // one more than is kept, to see whether the set ends
+            if taken.len() > precision {
+                exact = false;
+            }
+
+            let mut options = Vec::new();
+            for x in taken.iter().take(precision) {
+                match x {
+                    SurrealElement::Finite(s) => options.push(*s),
+                    SurrealElement::Infinite(s) => match s.classify(precision) {
+                        Approximation::Exact(v) => options.push(v),
+                        Approximation::Truncated(v) => {
+                            exact = false;
+                            options.push(v);
+                        }
+                        Approximation::Diverges => return None,
+                    },
+                }
+            }
+            Some(options)
+        };
+
+        let sets = truncate(&self.left).and_then(|l| Some((l, truncate(&self.right)?)));
+        match sets.and_then(|(l, r)| SurrealFinite::new(l, r)) {
+            Some(x) if exact => Approximation::Exact(x),
+            Some(x) => Approximation::Truncated(x),
+            None => Approximation::Diverges,
+        }
+    }
+
+    /// Tests whether the two numbers agree once both are truncated to their first `precision` options, returning `false` if either truncation isn't a number.
+    ///
+    /// This is a heuristic rather than true equality: numbers that differ only beyond the truncation compare as equal.
+    pub fn approx_eq(&self, other: &SurrealInfinite, precision: usize) -> bool {
+        match (self.to_finite(precision), other.to_finite(precision)) {
+            (Some(x), Some(y)) => x == y,
+            _ => false,
+        }
+    }
+
+    /// Tests whether the two numbers can be proven unequal, without truncating either to a guessed precision.
+    ///
+    /// When one of them has a known finite value `v`, the other's options are searched for a left option at least `v` or a right option at most `v`, either of which puts it strictly to one side of `v`. This tells a number that grows without bound, like `ω`, from every finite one. Only the first thousand or so options of each set are searched, and `false` means only that no proof was found, not that the numbers are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (omega, one) = (surreal::SurrealInfinite::omega(), surreal::SurrealFinite::one().to_infinite());
+    /// assert!(omega.definitely_not_eq(&one) && one.definitely_not_eq(&omega));
+    /// assert!(!one.definitely_not_eq(&one));
+    /// ```
+    pub fn definitely_not_eq(&self, other: &SurrealInfinite) -> bool {
+        match (self.value, other.value) {
+            (Some(x), Some(y)) => x != y,
+            (Some(v), None) => other.separated_from(v),
+            (None, Some(v)) => self.separated_from(v),
+            (None, None) => false,
+        }
+    }
+
+    // whether some option with a known value shows that this number isn't v
+    fn separated_from(&self, v: SurrealFinite) -> bool {
+        let separates = |set: &Rc<dyn SurrealIterator>, beyond: &dyn Fn(SurrealFinite) -> bool| {
+            let mut n = 1;
+            loop {
+                let taken = set.take(n);
+                if taken.iter().filter_map(exact_value).any(beyond) {
+                    return true;
+                }
+                if taken.len() < n || n >= MAX_SEPARATING_OPTIONS {
+                    return false;
+                }
+                n *= 2; // memoised, so each option is computed once however often it is taken
+            }
+        };
+
+        separates(&self.left, &|l| l >= v) || separates(&self.right, &|r| r <= v)
+    }
+
+    /// Tests whether this number is at most `other` once both are truncated to their first `precision` options, returning `false` if either truncation isn't a number.
+    ///
+    /// Like `approx_eq`, this is a heuristic and can only be trusted as far as the truncations are.
+    pub fn leq(&self, other: &SurrealInfinite, precision: usize) -> bool {
+        matches!(
+            self.partial_cmp_approx(other, precision),
+            Some(Ordering::Less) | Some(Ordering::Equal)
+        )
+    }
+
+    /// Orders the truncations of the two numbers to their first `precision` options, or returns `None` if either truncation isn't a number.
+    pub fn partial_cmp_approx(
+        &self,
+        other: &SurrealInfinite,
+        precision: usize,
+    ) -> Option<Ordering> {
+        match (self.to_finite(precision), other.to_finite(precision)) {
+            (Some(x), Some(y)) => Some(x.cmp(&y)),
+            _ => None,
+        }
+    }
+
+    pub fn to_element(&self) -> SurrealElement {
+        SurrealElement::Infinite(self.clone())
+    }
+
+    /// Returns the first `n` options of the left set, or fewer if the set ends before then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let left = surreal::SurrealInfinite::omega().iter_left(3);
+    /// assert!(left.iter().map(|x| x.as_finite().unwrap().stof()).eq(vec![1.0, 2.0, 3.0]));
+    /// ```
+    pub fn iter_left(&self, n: usize) -> Vec<SurrealElement> {
+        self.left.take(n)
+    }
+
+    /// Returns the first `n` options of the right set, or fewer if the set ends before then.
+    pub fn iter_right(&self, n: usize) -> Vec<SurrealElement> {
+        self.right.take(n)
+    }
+
+    /// Labels the number, so that it displays as `name`, and sums and products built from it display in terms of `name`.
+    pub fn with_name(self, name: &str) -> SurrealInfinite {
+        SurrealInfinite {
+            name: Some(String::from(name)),
+            ..self
+        }
+    }
+
+    /// Sets how many levels of nested sets `Display` shows before printing `…` in place of a number, 3 by default.
+    pub fn fmt_depth(self, depth: usize) -> SurrealInfinite {
+        SurrealInfinite {
+            fmt_depth: depth,
+            ..self
+        }
+    }
+
+    pub(crate) fn format(&self, depth: usize, show_options: bool) -> String {
+        match (self.value, &self.name) {
+            (Some(v), _) if !show_options => v.stof().to_string(),
+            (None, Some(n)) if !show_options => n.clone(),
+            _ if depth == 0 => String::from("…"),
+            _ => format!(
+                "< {}| {}>",
+                format_set(&*self.left, depth - 1),
+                format_set(&*self.right, depth - 1)
+            ),
+        }
+    }
+
+    fn symbol(&self) -> Option<String> {
+        match (&self.name, self.value) {
+            (Some(n), _) => Some(n.clone()),
+            (None, Some(v)) => Some(v.stof().to_string()),
+            (None, None) => None,
+        }
+    }
+
+    fn named(self, name: Option<String>) -> SurrealInfinite {
+        SurrealInfinite { name, ..self }
+    }
+}
+
+fn combine_names(x: &SurrealInfinite, op: &str, y: &SurrealInfinite) -> Option<String> {
+    // only numbers built entirely from named or finite ones are named, so the name always says what the number is
+    Some(format!("({} {} {})", x.symbol()?, op, y.symbol()?))
+}
+
+impl ops::Add<SurrealInfinite> for SurrealInfinite {
+    type Output = SurrealInfinite;
+
+    fn add(self, other: SurrealInfinite) -> SurrealInfinite {
+        if let (Some(x), Some(y)) = (self.value, other.value) {
+            return SurrealInfinite::from_finite(x + y); // stays exact, and prints as the sum
+        }
+
+        let name = combine_names(&self, "+", &other);
+        SurrealInfinite::new_raw(
+            SurrealZipSet::new_rc(vec![
+                SurrealAddSet::new_rc(self.to_element(), other.left.clone()),
+                SurrealAddSet::new_rc(other.to_element(), self.left.clone()),
+            ]),
+            SurrealZipSet::new_rc(vec![
+                SurrealAddSet::new_rc(self.to_element(), other.right.clone()),
+                SurrealAddSet::new_rc(other.to_element(), self.right.clone()),
+            ]),
+        )
+        .named(name)
+    }
+}
+
+impl ops::Neg for SurrealInfinite {
+    type Output = SurrealInfinite;
+
+    fn neg(self) -> SurrealInfinite {
+        SurrealInfinite {
+            name: self.name.as_ref().map(|n| format!("-{}", n)),
+            value: self.value.map(|v| -v), // so that subtracting a finite number still prints it exactly
+            ..SurrealInfinite::new_raw(
+                SurrealNegSet::new_rc(self.right),
+                SurrealNegSet::new_rc(self.left),
+            )
+        }
+    }
+}
+
+impl ops::Sub<SurrealInfinite> for SurrealInfinite {
+    type Output = SurrealInfinite;
+
+    fn sub(self, other: SurrealInfinite) -> SurrealInfinite {
+        if let (Some(x), Some(y)) = (self.value, other.value) {
+            return SurrealInfinite::from_finite(x - y);
+        }
+
+        let name = combine_names(&self, "-", &other);
+        (self + (-other)).named(name)
+    }
+}
+
+impl ops::Mul<SurrealInfinite> for SurrealInfinite {
+    type Output = SurrealInfinite;
+
+    fn mul(self, other: SurrealInfinite) -> SurrealInfinite {
+        if let (Some(x), Some(y)) = (self.value, other.value) {
+            return SurrealInfinite::from_finite(x * y);
+        }
+
+        let name = combine_names(&self, "*", &other);
+        let (x, y) = (self.to_element(), other.to_element());
+        SurrealInfinite::new_raw(
+            SurrealZipSet::new_rc(vec![
+                SurrealMulSet::new_rc(x.clone(), y.clone(), self.left.clone(), other.left.clone()),
+                SurrealMulSet::new_rc(
+                    x.clone(),
+                    y.clone(),
+                    self.right.clone(),
+                    other.right.clone(),
+                ),
+            ]),
+            SurrealZipSet::new_rc(vec![
+                SurrealMulSet::new_rc(x.clone(), y.clone(), self.left.clone(), other.right.clone()),
+                SurrealMulSet::new_rc(x, y, self.right.clone(), other.left.clone()),
+            ]),
+        )
+        .named(name)
+    }
+}
+
+impl ops::AddAssign for SurrealInfinite {
+    fn add_assign(&mut self, other: SurrealInfinite) {
+        *self = self.clone() + other;
+    }
+}
+
+impl ops::SubAssign for SurrealInfinite {
+    fn sub_assign(&mut self, other: SurrealInfinite) {
+        *self = self.clone() - other;
+    }
+}
+
+impl ops::MulAssign for SurrealInfinite {
+    fn mul_assign(&mut self, other: SurrealInfinite) {
+        *self = self.clone() * other; // keeps the finite short-circuit in mul
+    }
+}
+
+// todo Rem
+
+impl fmt::Display for SurrealInfinite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // the alternate form always shows the options
+        write!(f, "{}", self.format(self.fmt_depth, f.alternate()))
+    }
+}
+
+/// Parses the names `Display` prints for the named numbers, `ω` and `ϵ` and their negations, or a finite number written as `SurrealFinite` parses it or as a single float, like the value of a finite `SurrealInfinite` is printed.
+///
+/// Other numbers are printed with their sets truncated, so they can't be parsed back.
+///
+/// # Examples
+///
+/// ```
+/// let omega: surreal::SurrealInfinite = "ω".parse().unwrap();
+/// assert!(omega.to_string() == "ω");
+/// assert!("-ϵ".parse::<surreal::SurrealInfinite>().unwrap().to_string() == "-ϵ");
+/// assert!("2.5".parse::<surreal::SurrealInfinite>().unwrap().to_string() == "2.5");
+/// assert!("ω + 1".parse::<surreal::SurrealInfinite>().is_err());
+/// ```
+impl FromStr for SurrealInfinite {
+    type Err = ParseSurrealError;
 
+    fn from_str(s: &str) -> Result<SurrealInfinite, ParseSurrealError> {
+        match s.trim() {
+            "ω" => Ok(SurrealInfinite::omega()),
+            "-ω" => Ok(-SurrealInfinite::omega()),
+            "ϵ" => Ok(SurrealInfinite::epsilon()),
+            "-ϵ" => Ok(-SurrealInfinite::epsilon()),
+            s => parse_option(s).map(SurrealInfinite::from_finite),
+        }
+    }
 }
@@ -1,13 +1,63 @@
 //! Implementation of J. H. Conway's surreal numbers, as explained in the book *[Surreal Numbers](https://www.amazon.com/dp/0201038129)* by Donald Knuth.
+//!
+//! Without the default `std` feature the crate is `no_std`, and only needs `alloc`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate core;
+#[cfg(not(feature = "std"))]
+extern crate hashbrown;
+#[cfg(feature = "num-bigint")]
+extern crate num_bigint;
+#[cfg(feature = "num-bigint")]
+extern crate num_rational;
+#[cfg(feature = "num-traits")]
+extern crate num_traits;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(not(feature = "std"))]
+extern crate spin;
+#[cfg(all(test, not(feature = "std")))]
+extern crate std; // for the test harness
 
 #[macro_use]
-extern crate lazy_static;
+mod sync;
 
+#[macro_use]
+mod macros;
+
+pub mod consts;
 mod finite;
+mod game;
 mod infinite;
+pub mod prelude;
+
+#[cfg(feature = "rayon")]
+pub use finite::generation_par;
+#[cfg(feature = "proptest")]
+pub use finite::MaxBirthday;
+pub use finite::{
+    cache_stats, clear_caches, div, div_approx, ftos, ftos_with_tolerance, generation, integer,
+    leq, max_options, pairwise_products, pairwise_sums, set_max_options, simplest_between,
+    verify_field_laws, CacheStats, DivError, LawViolation, NotANumber, ParseSurrealError,
+    Structure, SurrealBuilder, SurrealFinite, TryFromFloatError, TryNewError,
+};
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use finite::{dump_cache, load_cache};
+pub use game::{GameOrdering, SurrealGame, SurrealOrdering};
+pub use infinite::{Approximation, SurrealElement, SurrealInfinite};
 
-pub use finite::{div_approx, ftos, SurrealFinite};
-pub use infinite::{SurrealInfinite, SurrealElement};
+#[doc(hidden)]
+pub use macros::{build as __surreal_build, SurrealLiteral as __SurrealLiteral};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,120 @@
+use core::convert::TryFrom;
+
+use finite::SurrealFinite;
+
+/// Builds a `SurrealFinite` from its left and right sets, written like `surreal!{ 0, 1 | 3 }`.
+///
+/// Members are separated by commas, and may be integer or float literals, any expression evaluating to a `SurrealFinite`, or nested sets in braces. Floats are converted exactly, so they must be short dyadic fractions.
+///
+/// # Panics
+///
+/// Panics if a float can't be converted exactly, or if the sets don't form a number.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use]
+/// extern crate surreal;
+///
+/// # fn main() {
+/// assert!(surreal!{ | } == surreal::SurrealFinite::zero());
+/// assert!(surreal!{ 0, 1 | 3 } == surreal::ftos(2.0));
+/// assert!(surreal!{ { | } | { 1 | } } == surreal::ftos(1.0));
+/// assert!(surreal!{ -0.5 | surreal::ftos(0.25) } == surreal::SurrealFinite::zero());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! surreal {
+    ($($body:tt)*) => {
+        $crate::__surreal_sets!(@left [] [] $($body)*)
+    };
+}
+
+// splits the tokens into members at each top-level comma, and into sides at the bar
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __surreal_sets {
+    (@left [$($l:tt)*] [] | $($rest:tt)*) => {
+        $crate::__surreal_sets!(@right [$($l)*] [] [] $($rest)*)
+    };
+    (@left [$($l:tt)*] [$($m:tt)+] | $($rest:tt)*) => {
+        $crate::__surreal_sets!(@right [$($l)* [$($m)+]] [] [] $($rest)*)
+    };
+    (@left [$($l:tt)*] [$($m:tt)+] , $($rest:tt)*) => {
+        $crate::__surreal_sets!(@left [$($l)* [$($m)+]] [] $($rest)*)
+    };
+    (@left [$($l:tt)*] [$($m:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__surreal_sets!(@left [$($l)*] [$($m)* $next] $($rest)*)
+    };
+    (@right [$($l:tt)*] [$($r:tt)*] [$($m:tt)+] , $($rest:tt)*) => {
+        $crate::__surreal_sets!(@right [$($l)*] [$($r)* [$($m)+]] [] $($rest)*)
+    };
+    (@right [$($l:tt)*] [$($r:tt)*] [$($m:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__surreal_sets!(@right [$($l)*] [$($r)*] [$($m)* $next] $($rest)*)
+    };
+    (@right [$($l:tt)*] [$($r:tt)*] [$($m:tt)+]) => {
+        $crate::__surreal_sets!(@right [$($l)*] [$($r)* [$($m)+]] [])
+    };
+    (@right [$([$($l:tt)+])*] [$([$($r:tt)+])*] []) => {
+        $crate::__surreal_build(
+            &[$($crate::__surreal_member!($($l)+)),*],
+            &[$($crate::__surreal_member!($($r)+)),*],
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __surreal_member {
+    ({ $($inner:tt)* }) => {
+        $crate::surreal!($($inner)*)
+    };
+    ($e:expr) => {
+        $crate::__SurrealLiteral::into_surreal($e)
+    };
+}
+
+/// The types that may appear as members in `surreal!`.
+#[doc(hidden)]
+pub trait SurrealLiteral {
+    fn into_surreal(self) -> SurrealFinite;
+}
+
+impl SurrealLiteral for SurrealFinite {
+    fn into_surreal(self) -> SurrealFinite {
+        self
+    }
+}
+
+macro_rules! impl_literal_int {
+    ($($t:ty)*) => {$(
+        impl SurrealLiteral for $t {
+            fn into_surreal(self) -> SurrealFinite {
+                SurrealFinite::from(self)
+            }
+        }
+    )*};
+}
+
+impl_literal_int! { i8 i16 i32 i64 u8 u16 u32 u64 }
+
+impl SurrealLiteral for f64 {
+    fn into_surreal(self) -> SurrealFinite {
+        SurrealFinite::try_from(self).unwrap_or_else(|error| {
+            panic!("float {} in surreal! can't be converted: {}", self, error)
+        })
+    }
+}
+
+impl SurrealLiteral for f32 {
+    fn into_surreal(self) -> SurrealFinite {
+        f64::from(self).into_surreal()
+    }
+}
+
+#[doc(hidden)]
+pub fn build(left: &[SurrealFinite], right: &[SurrealFinite]) -> SurrealFinite {
+    SurrealFinite::new(left.to_vec(), right.to_vec()).expect(
+        "sets in surreal! don't form a number, as a left member isn't below every right one",
+    )
+}
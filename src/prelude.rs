@@ -0,0 +1,18 @@
+//! The types and functions most programs need, to be glob imported.
+//!
+//! # Examples
+//!
+//! ```
+//! use surreal::prelude::*;
+//!
+//! let x = ftos(1.5) + consts::half();
+//! let y = surreal!{ 1 | }; // 2
+//! assert!(x + y == integer(4));
+//! ```
+
+pub use consts;
+pub use finite::{div, div_approx, ftos, generation, integer, leq, simplest_between};
+pub use finite::{DivError, SurrealBuilder, SurrealFinite};
+pub use game::SurrealGame;
+pub use infinite::{Approximation, SurrealElement, SurrealInfinite};
+pub use surreal;
@@ -0,0 +1,210 @@
+//! The locks and maps behind the global caches: those of `std` when it's available, and otherwise those of `spin` and `hashbrown`.
+//!
+//! On wasm without atomics there is only ever one thread, so the caches are kept in plain `RefCell`s instead.
+//!
+//! With the `thread-local-memos` feature, the memo tables of `<=` and the arithmetic operators are kept per thread, so they are never locked.
+
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", not(target_feature = "atomics")))
+))]
+pub(crate) use self::std_lock::RwLock;
+#[cfg(feature = "std")]
+pub(crate) use std::collections::HashMap;
+
+#[cfg(all(
+    not(feature = "std"),
+    not(all(target_arch = "wasm32", not(target_feature = "atomics")))
+))]
+pub(crate) use self::spin_lock::RwLock;
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::HashMap;
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+pub(crate) use self::local_lock::RwLock;
+
+#[cfg(feature = "std")]
+pub(crate) use std::sync::LazyLock as Lazy;
+
+#[cfg(not(feature = "std"))]
+pub(crate) use spin::Lazy;
+
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", not(target_feature = "atomics")))
+))]
+mod std_lock {
+    use std::convert::Infallible;
+    use std::sync::{self, PoisonError, RwLockReadGuard, RwLockWriteGuard};
+
+    /// A `std::sync::RwLock` that recovers from poisoning, so a panic while a cache is locked doesn't disable it for the rest of the process.
+    ///
+    /// Every entry is written whole, so a cache is never left holding a partial one.
+    pub(crate) struct RwLock<T>(sync::RwLock<T>);
+
+    impl<T> RwLock<T> {
+        pub(crate) const fn new(value: T) -> RwLock<T> {
+            RwLock(sync::RwLock::new(value))
+        }
+
+        pub(crate) fn read(&self) -> Result<RwLockReadGuard<'_, T>, Infallible> {
+            Ok(self.0.read().unwrap_or_else(PoisonError::into_inner))
+        }
+
+        pub(crate) fn write(&self) -> Result<RwLockWriteGuard<'_, T>, Infallible> {
+            Ok(self.0.write().unwrap_or_else(PoisonError::into_inner))
+        }
+    }
+}
+
+#[cfg(all(
+    not(feature = "std"),
+    not(all(target_arch = "wasm32", not(target_feature = "atomics")))
+))]
+mod spin_lock {
+    use core::convert::Infallible;
+
+    use spin::{RwLockReadGuard, RwLockWriteGuard};
+
+    /// A spin lock with the interface of `std::sync::RwLock`, which can never be poisoned.
+    pub(crate) struct RwLock<T>(spin::RwLock<T>);
+
+    impl<T> RwLock<T> {
+        pub(crate) const fn new(value: T) -> RwLock<T> {
+            RwLock(spin::RwLock::new(value))
+        }
+
+        pub(crate) fn read(&self) -> Result<RwLockReadGuard<'_, T>, Infallible> {
+            Ok(self.0.read())
+        }
+
+        pub(crate) fn write(&self) -> Result<RwLockWriteGuard<'_, T>, Infallible> {
+            Ok(self.0.write())
+        }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+mod local_lock {
+    use core::cell::{RefCell, RefMut};
+    use core::convert::Infallible;
+
+    pub(crate) use core::cell::Ref as RwLockReadGuard;
+
+    /// A `RefCell` with the interface of `std::sync::RwLock`, for targets with a single thread.
+    ///
+    /// Taking the write lock while a read lock is held panics, where a real lock would deadlock.
+    pub(crate) struct RwLock<T>(RefCell<T>);
+
+    // without atomics, wasm can't start a second thread to share the cell with
+    unsafe impl<T> Sync for RwLock<T> {}
+
+    impl<T> RwLock<T> {
+        pub(crate) const fn new(value: T) -> RwLock<T> {
+            RwLock(RefCell::new(value))
+        }
+
+        pub(crate) fn read(&self) -> Result<RwLockReadGuard<'_, T>, Infallible> {
+            Ok(self.0.borrow())
+        }
+
+        pub(crate) fn write(&self) -> Result<RefMut<'_, T>, Infallible> {
+            Ok(self.0.borrow_mut())
+        }
+    }
+}
+
+// declares statics that are initialised on first use
+macro_rules! global {
+    ($(static $name:ident: $t:ty = $init:expr;)*) => {
+        $(static $name: $crate::sync::Lazy<$t> = $crate::sync::Lazy::new(|| $init);)*
+    };
+}
+
+/// A memo table shared by every thread behind a lock.
+#[cfg(not(feature = "thread-local-memos"))]
+pub(crate) struct Memo<T>(RwLock<T>);
+
+#[cfg(not(feature = "thread-local-memos"))]
+impl<T: Default> Memo<T> {
+    pub(crate) fn new(value: T) -> Memo<T> {
+        Memo(RwLock::new(value))
+    }
+
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.0.read().unwrap())
+    }
+
+    pub(crate) fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.0.write().unwrap())
+    }
+
+    pub(crate) fn clear(&self) {
+        self.with_mut(|memo| *memo = T::default());
+    }
+}
+
+/// A memo table of which every thread has its own copy, so it is never locked.
+///
+/// Clearing it clears the calling thread's copy, and every other thread's the next time that thread uses it.
+#[cfg(feature = "thread-local-memos")]
+pub(crate) struct Memo<T: 'static>(&'static std::thread::LocalKey<core::cell::RefCell<(usize, T)>>);
+
+// counts the clears of every memo table, so each thread can tell when its copies are stale
+#[cfg(feature = "thread-local-memos")]
+static CLEARS: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+#[cfg(feature = "thread-local-memos")]
+impl<T: Default> Memo<T> {
+    pub(crate) const fn new(
+        local: &'static std::thread::LocalKey<core::cell::RefCell<(usize, T)>>,
+    ) -> Memo<T> {
+        Memo(local)
+    }
+
+    fn refresh(cell: &core::cell::RefCell<(usize, T)>) {
+        let clears = CLEARS.load(core::sync::atomic::Ordering::Acquire);
+        if cell.borrow().0 != clears {
+            *cell.borrow_mut() = (clears, T::default());
+        }
+    }
+
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        self.0.with(|cell| {
+            Memo::refresh(cell);
+            f(&cell.borrow().1)
+        })
+    }
+
+    pub(crate) fn with_mut<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        self.0.with(|cell| {
+            Memo::refresh(cell);
+            f(&mut cell.borrow_mut().1)
+        })
+    }
+
+    pub(crate) fn clear(&self) {
+        CLEARS.fetch_add(1, core::sync::atomic::Ordering::AcqRel);
+        self.with_mut(|_| {}); // clears this thread's copy straight away, reclaiming its memory
+    }
+}
+
+// declares memo tables, global or per thread
+macro_rules! memos {
+    ($(static $name:ident: $t:ty = $init:expr;)*) => {
+        #[cfg(not(feature = "thread-local-memos"))]
+        global! {
+            $(static $name: $crate::sync::Memo<$t> = $crate::sync::Memo::new($init);)*
+        }
+
+        $(
+            #[cfg(feature = "thread-local-memos")]
+            static $name: $crate::sync::Memo<$t> = {
+                thread_local! {
+                    static LOCAL: ::core::cell::RefCell<(usize, $t)> = ::core::cell::RefCell::new((0, $init));
+                }
+                $crate::sync::Memo::new(&LOCAL)
+            };
+        )*
+    };
+}
@@ -1,36 +1,42 @@
-use super::ftos;
-use super::SurrealFinite;
-use super::SurrealInfinite;
-
-fn day_gen(days: i32) -> Vec<SurrealFinite> {
-    if days == 1 {
-        return vec![SurrealFinite::zero()];
-    }
-
-    let v = day_gen(days - 1);
-    let mut w = vec![];
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::rc::Rc;
+#[allow(unused_imports)]
+use std::{prelude::v1::*, println}; // not in scope by default when the crate is built without std
 
-    w.push(SurrealFinite::new(vec![], vec![v[0]]).unwrap());
-    for i in 0..v.len() {
-        w.push(v[i]);
-        if i != v.len() - 1 {
-            w.push(SurrealFinite::new(vec![v[i]], vec![v[i + 1]]).unwrap());
-        }
-    }
-    w.push(SurrealFinite::new(vec![v[v.len() - 1]], vec![]).unwrap());
+use super::finite::construction::{
+    cache_insert, cache_insert_at, cache_left, cache_right, checked_dyadic_parts, SurrealStructure,
+};
+use super::infinite::iterators::{SurrealBasicSet, SurrealIterator, SurrealZipSet};
+use super::sync::{HashMap, RwLock};
+use super::SurrealFinite;
+use super::{
+    div, div_approx, ftos, ftos_with_tolerance, generation, integer, leq, pairwise_products,
+    pairwise_sums, simplest_between, verify_field_laws, DivError, LawViolation, NotANumber,
+    ParseSurrealError, SurrealBuilder, TryFromFloatError, TryNewError,
+};
+use super::{Approximation, SurrealElement, SurrealInfinite};
+use super::{GameOrdering, SurrealGame, SurrealOrdering};
 
-    w
+#[test]
+fn generation_order() {
+    let v = generation(3);
+    assert!(v.len() == 15);
+    assert!(v.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(v.iter().all(|x| x.birthday() <= 3));
+    assert!(generation(0) == vec![SurrealFinite::zero()]);
 }
 
 #[test]
 fn leq_theorems() {
-    let v = day_gen(6); // chosen for efficiency
+    let v = generation(5); // chosen for efficiency
 
     for i in 0..v.len() {
         assert!(v[i] <= v[i]); // T3
 
         for j in 0..v.len() {
-            if !(v[i] <= v[j]) {
+            if v[i] > v[j] {
                 assert!(v[j] <= v[i]); // T4
             }
 
@@ -38,8 +44,8 @@ fn leq_theorems() {
                 if v[i] <= v[j] && v[j] <= v[k] {
                     assert!(v[i] <= v[k]); // T1
 
-                    if &v[i] < &v[j] || &v[j] < &v[k] {
-                        assert!(&v[i] < &v[k]); // T5 & T6
+                    if v[i] < v[j] || v[j] < v[k] {
+                        assert!(v[i] < v[k]); // T5 & T6
                     }
                 }
             }
@@ -47,9 +53,43 @@ fn leq_theorems() {
     }
 }
 
+#[test]
+fn cmp_agrees_with_leq() {
+    let order = |x: &SurrealFinite, y: &SurrealFinite| match (leq(x, y), leq(y, x)) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        _ => Ordering::Greater,
+    };
+
+    // compared by their floats, including a form whose options are far from its value
+    let wide = SurrealFinite::new(vec![ftos(-7.5)], vec![ftos(100.0)]).unwrap(); // 0
+    let v = vec![
+        ftos(-100.0),
+        ftos(-3.25),
+        wide,
+        ftos(0.75),
+        integer(64),
+        ftos(300.0),
+    ];
+    for x in &v {
+        for y in &v {
+            assert!(x.cmp(y) == order(x, y));
+        }
+    }
+    assert!(wide.cmp(&SurrealFinite::zero()) == Ordering::Equal);
+
+    // beyond the precision of floats, so compared exactly
+    let mut signs = vec![true, true];
+    signs.extend(vec![false; 60]);
+    let close = SurrealFinite::from_sign_expansion(&signs); // 1 + 2^-59
+    assert!(close.stof() != 1.0); // rounding in the options throws the float off, past even the nearest float
+    assert!(close.cmp(&SurrealFinite::one()) == Ordering::Greater);
+    assert!(SurrealFinite::one().cmp(&close) == order(&SurrealFinite::one(), &close));
+}
+
 #[test]
 fn add_theorems() {
-    let v = day_gen(4);
+    let v = generation(3);
 
     for i in 0..v.len() {
         assert!(v[i] + SurrealFinite::zero() == v[i]); // T10
@@ -68,9 +108,55 @@ fn add_theorems() {
     }
 }
 
+#[cfg(feature = "rayon")]
+mod parallel {
+    use rayon::prelude::*;
+
+    use super::super::{generation, generation_par, SurrealFinite};
+
+    #[test]
+    fn generation_par_matches() {
+        let par = generation_par(5);
+        let seq = generation(5);
+        assert!(par.len() == seq.len());
+        for (x, y) in par.iter().zip(&seq) {
+            assert!(x.hash == y.hash); // the very same cached numbers, not just equal ones
+        }
+    }
+
+    #[test]
+    fn add_theorems_par() {
+        let v = generation_par(3);
+
+        // each thread takes the pairs for one i, so the caches see many concurrent inserts
+        (0..v.len()).into_par_iter().for_each(|i| {
+            assert!(v[i] + SurrealFinite::zero() == v[i]); // T10
+            for j in 0..v.len() {
+                assert!(v[i] + v[j] == v[j] + v[i]); // T9
+                for k in 0..v.len() {
+                    assert!((v[i] + v[j]) + v[k] == v[i] + (v[j] + v[k])); // T11
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn mul_theorems_par() {
+        let v = generation_par(2);
+
+        (0..v.len()).into_par_iter().for_each(|i| {
+            assert!(v[i] * SurrealFinite::one() == v[i]); // T22
+            for j in 0..v.len() {
+                assert!(v[i] * v[j] == v[j] * v[i]); // T20
+                assert!(-(v[i] * v[j]) == (-v[i]) * v[j]); // T23
+            }
+        });
+    }
+}
+
 #[test]
 fn neg_theorems() {
-    let v = day_gen(4);
+    let v = generation(3);
 
     for i in 0..v.len() {
         assert!(v[i] - v[i] == SurrealFinite::zero()); // T15
@@ -83,9 +169,55 @@ fn neg_theorems() {
     }
 }
 
+#[cfg(feature = "proptest")]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::super::{MaxBirthday, SurrealFinite};
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn distributive(
+            a in any_with::<SurrealFinite>(MaxBirthday(3)),
+            b in any::<SurrealFinite>(),
+            c in any::<SurrealFinite>(),
+        ) {
+            prop_assert!(a * (b + c) == a * b + a * c);
+        }
+
+        #[test]
+        fn add_commutative_associative(a: SurrealFinite, b: SurrealFinite, c: SurrealFinite) {
+            prop_assert!(a + b == b + a);
+            prop_assert!((a + b) + c == a + (b + c));
+        }
+
+        #[test]
+        fn birthday_bounded(x in any_with::<SurrealFinite>(MaxBirthday(6))) {
+            prop_assert!(x.birthday() <= 6);
+        }
+    }
+
+    #[test]
+    fn shrinks_towards_zero() {
+        use proptest::test_runner::{TestError, TestRunner};
+
+        let mut runner = TestRunner::default();
+        let result = runner.run(&any_with::<SurrealFinite>(MaxBirthday(8)), |x| {
+            prop_assert!(x.birthday() < 3);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, x)) => assert!(x.birthday() == 3), // as close to zero as still fails
+            _ => panic!("numbers born on day 3 should have been generated"),
+        }
+    }
+}
+
 #[test]
 fn mul_theorems() {
-    let v = day_gen(3);
+    let v = generation(2);
 
     for i in 0..v.len() {
         assert!(v[i] * SurrealFinite::zero() == SurrealFinite::zero()); // T21
@@ -98,40 +230,1679 @@ fn mul_theorems() {
     }
 }
 
+#[test]
+fn field_laws() {
+    assert!(verify_field_laws(&generation(3)) == Ok(()));
+    assert!(verify_field_laws(&[]) == Ok(()));
+
+    let violation = LawViolation::Distributivity {
+        a: ftos(0.5),
+        b: ftos(-1.0),
+        c: ftos(2.0),
+    };
+    assert!(violation.to_string() == "a * (b + c) != a * b + a * c for a = 0.5, b = -1, c = 2");
+}
+
+#[test]
+fn pairwise() {
+    let v = generation(3);
+    let (sums, products) = (pairwise_sums(&v), pairwise_products(&v));
+    assert!(sums.len() == v.len() * v.len() && products.len() == sums.len());
+    for (i, &x) in v.iter().enumerate() {
+        for (j, &y) in v.iter().enumerate() {
+            assert!(sums[i * v.len() + j] == x + y);
+            assert!(products[i * v.len() + j] == x * y);
+        }
+    }
+    assert!(pairwise_sums(&[]).is_empty() && pairwise_products(&[ftos(3.0)]) == vec![ftos(9.0)]);
+}
+
 #[test]
 fn stof_ftos() {
-    let v = day_gen(7);
+    let v = generation(6);
 
-    for i in 0..v.len() {
-        assert!(v[i] == ftos(v[i].stof()));
+    for x in &v {
+        assert!(*x == ftos(x.stof()));
     }
+
+    // options needn't be adjacent to the number in the tree
+    let new = |l: &[f64], r: &[f64]| {
+        SurrealFinite::new(
+            l.iter().map(|&x| ftos(x)).collect(),
+            r.iter().map(|&x| ftos(x)).collect(),
+        )
+        .unwrap()
+    };
+    assert!(new(&[0.5], &[4.0]).stof() == 1.0);
+    assert!(new(&[-2.5], &[]).stof() == 0.0 && new(&[2.5], &[]).stof() == 3.0);
+    assert!(new(&[-4.0], &[-1.0]).stof() == -2.0);
+    assert!(new(&[1.25], &[1.75]).stof() == 1.5 && new(&[-1.3125], &[-1.25]).stof() == -1.28125);
 }
 
 #[test]
-fn omega() {
-    println!("ω = {}", SurrealInfinite::omega());
-    println!("ϵ = {}", SurrealInfinite::epsilon());
-    println!("-ω = {}", -SurrealInfinite::omega());
-    println!(
-        "2 - 1 = {}",
-        ftos(2.0).to_infinite() - SurrealFinite::one().to_infinite()
+fn stof32() {
+    for f in [0.0f32, 0.5, -3.75, 1.0 + f32::EPSILON, 1e-40, -255.75] {
+        assert!(ftos_with_tolerance(f64::from(f), 0.0).stof32().to_bits() == f.to_bits());
+    }
+    assert!(ftos(1.0 + 2f64.powi(-24) + 2f64.powi(-26)).stof32() == 1.0 + f32::EPSILON);
+    assert!(ftos(1.0 + 2f64.powi(-24)).stof32() == 1.0); // halfway, to even
+    assert!(ftos(-(1.0 + 3.0 * 2f64.powi(-24))).stof32() == -(1.0 + 2.0 * f32::EPSILON));
+    let exact = |f: f64| ftos_with_tolerance(f, 0.0);
+    assert!(exact(2f64.powi(-150)).stof32() == 0.0 && exact(3.0 * 2f64.powi(-151)).stof32() > 0.0);
+
+    // just past halfway by less than an f64 can hold, so going through the nearest f64 would land on halfway
+    let mut signs = vec![true, true, false];
+    signs.extend(vec![false; 23]);
+    signs.push(true);
+    signs.extend(vec![false; 35]);
+    let x = SurrealFinite::from_sign_expansion(&signs);
+    assert!(x.dyadic_parts() == (1 << 60 | 1 << 36 | 1, 1 << 60)); // 1 + 2^-24 + 2^-60
+    assert!((1.0 + 2f64.powi(-24) + 2f64.powi(-60)) as f32 == 1.0);
+    assert!(x.stof32() == 1.0 + f32::EPSILON);
+}
+
+#[test]
+fn builder() {
+    let mut builder = SurrealBuilder::new();
+    assert!(builder.is_empty() && builder.value() == SurrealFinite::zero());
+    assert!(builder.push(true).value() == SurrealFinite::one());
+
+    builder.push(false);
+    assert!(builder.value() == ftos(0.5));
+    builder.push(true);
+    assert!(builder.len() == 3 && builder.value().birthday() == 3);
+    assert!(builder.build() == ftos(0.75));
+    assert!(builder.build().sign_expansion() == vec![true, false, true]);
+
+    // every walk lands where from_sign_expansion does
+    let signs = [false, false, true, false, true, true];
+    let mut walked = SurrealBuilder::default();
+    walked.extend(signs.iter().cloned());
+    assert!(walked.build() == SurrealFinite::from_sign_expansion(&signs));
+    assert!(walked.build().hash == SurrealFinite::from_sign_expansion(&signs).hash);
+}
+
+#[test]
+fn signed_zero() {
+    let zero = SurrealFinite::zero();
+    assert!(ftos(0.0).hash == zero.hash && ftos(-0.0).hash == zero.hash);
+    assert!(SurrealFinite::try_from(-0.0) == Ok(zero));
+
+    // every form of zero converts back to positive zero
+    let forms = vec![
+        zero,
+        ftos(-0.0),
+        -zero,
+        SurrealFinite::new(vec![], vec![ftos(1.0)]).unwrap(),
+        SurrealFinite::new(vec![ftos(-1.0)], vec![]).unwrap(),
+        SurrealFinite::new(vec![ftos(-0.5)], vec![ftos(2.0)]).unwrap(),
+        ftos(0.5) + -ftos(0.5),
+    ];
+    for x in forms {
+        assert!(x == zero && x.stof().to_bits() == 0.0f64.to_bits());
+    }
+}
+
+#[test]
+fn ftos_tolerance() {
+    assert!(ftos_with_tolerance(0.3, 0.01) == ftos(0.296875));
+    assert!(ftos_with_tolerance(-0.3, 0.1) == ftos(-0.25));
+    assert!(ftos_with_tolerance(1000.2, 0.001) == ftos(1000.0)); // relative to the magnitude
+    assert!(ftos_with_tolerance(0.1, f64::EPSILON) != ftos(0.1));
+    assert!(ftos(1e-20).stof() == 1e-20 && ftos_with_tolerance(1e-20, f64::EPSILON).stof() == 0.0);
+
+    // by default every float converts exactly, whatever its magnitude
+    for f in [0.1, -0.3, 1000.1, -1e-5, 1e-300] {
+        assert!(ftos(f).stof() == f);
+    }
+
+    // conversions that would never finish, or exhaust memory, are rejected promptly
+    for f in [1e15, -1e15, f64::MAX, f64::INFINITY, f64::NAN] {
+        assert!(std::panic::catch_unwind(|| ftos(f)).is_err());
+    }
+}
+
+#[test]
+fn dyadic_parts() {
+    assert!(ftos(0.75).dyadic_parts() == (3, 4));
+    assert!(ftos(-2.375).dyadic_parts() == (-19, 8));
+    assert!(SurrealFinite::zero().dyadic_parts() == (0, 1));
+    assert!(ftos(5.0).is_integer());
+    assert!(!ftos(5.5).is_integer());
+
+    for x in generation(5) {
+        let (n, d) = x.dyadic_parts();
+        assert!(n as f64 / d as f64 == x.stof());
+        assert!(x.is_integer() == (d == 1));
+        assert!(x.is_dyadic());
+    }
+}
+
+#[test]
+fn integer_arithmetic() {
+    // built directly rather than by recursing through every smaller pair of integers
+    assert!(ftos(300.0) + ftos(-200.0) == ftos(100.0));
+    assert!(ftos(40.0) * ftos(-25.0) == ftos(-1000.0));
+    assert!((ftos(-7.0) * ftos(-6.0)).dyadic_parts() == (42, 1));
+    assert!(ftos(3.0) + ftos(0.5) == ftos(3.5)); // only pairs of integers take the shortcut
+}
+
+#[test]
+fn integer_near_furthest() {
+    // later integers step on or back from the furthest built, instead of up from the table again
+    for &n in &[3000, 2990, 3010, 2000, -3000, -2999, -65, 65] {
+        let x = integer(n);
+        assert!(x.stof() == n as f64);
+        assert!(x.is_integer() && x.birthday() == n.unsigned_abs());
+        assert!(x == ftos(n as f64));
+    }
+    assert!(integer(2990).sets() == (vec![integer(2989)], vec![]));
+}
+
+#[test]
+fn fmt_exact() {
+    let x = ftos(0.375);
+    let exact = format!("{:#}", x);
+    assert!(exact == "< 1/4 | 1/2 >");
+    assert!(exact.parse::<SurrealFinite>().unwrap() == x);
+    assert!(format!("{:#}", ftos(-2.0)) == "< | -1 >");
+    assert!("< 1/3 | >".parse::<SurrealFinite>() == Err(ParseSurrealError::Syntax));
+
+    for x in generation(4) {
+        assert!(format!("{:#}", x).parse::<SurrealFinite>().unwrap() == x);
+    }
+}
+
+#[test]
+fn structure_string() {
+    assert!(SurrealFinite::zero().to_structure_string(None) == "{ | }");
+    assert!(SurrealFinite::one().to_structure_string(None) == "{ { | } | }");
+    assert!(ftos(-0.5).to_structure_string(None) == "{ { | { | } } | { | } }");
+    assert!(ftos(-0.5).to_structure_string(Some(0)) == "{ ... | ... }");
+    assert!(SurrealFinite::zero().to_structure_string(Some(0)) == "{ | }");
+
+    let deep = SurrealFinite::from(5000).to_structure_string(None);
+    assert!(deep.matches("{ | }").count() == 1); // built without overflowing the stack
+}
+
+#[test]
+fn valid_numbers() {
+    let (zero, one, half) = (ftos(0.0), ftos(1.0), ftos(0.5));
+    assert!(SurrealFinite::is_valid_number(&[], &[]));
+    assert!(SurrealFinite::is_valid_number(&[zero], &[]));
+    assert!(SurrealFinite::is_valid_number(
+        &[zero, half],
+        &[one, ftos(2.0)]
+    ));
+
+    assert!(!SurrealFinite::is_valid_number(&[zero], &[zero]));
+    assert!(!SurrealFinite::is_valid_number(&[zero, one], &[half]));
+    assert!(!SurrealFinite::is_valid_number(&[one], &[zero, ftos(2.0)]));
+
+    for (left, right) in [(vec![zero], vec![one]), (vec![one], vec![half])] {
+        let valid = SurrealFinite::is_valid_number(&left, &right);
+        assert!(valid == SurrealFinite::new(left, right).is_some());
+    }
+}
+
+#[test]
+fn not_a_number() {
+    let (zero, one, half) = (ftos(0.0), ftos(1.0), ftos(0.5));
+    assert!(SurrealFinite::try_new(vec![zero], vec![one]) == Ok(half));
+
+    let error = SurrealFinite::try_new(vec![zero, one], vec![half, ftos(2.0)]).unwrap_err();
+    assert!(
+        error
+            == TryNewError::NotANumber(NotANumber {
+                left: one,
+                right: half
+            })
+    );
+    assert!(error.to_string() == "left option 1 is not less than right option 0.5");
+
+    let error = SurrealFinite::try_new(vec![zero], vec![zero]).unwrap_err();
+    assert!(
+        error
+            == NotANumber {
+                left: zero,
+                right: zero
+            }
+            .into()
+    );
+}
+
+#[test]
+fn simplest_between_theorem() {
+    let (zero, one) = (ftos(0.0), ftos(1.0));
+    assert!(simplest_between(zero, ftos(2.0)) == Some(one));
+    assert!(simplest_between(zero, one) == Some(ftos(0.5)));
+    assert!(simplest_between(ftos(-5.5), ftos(3.0)) == Some(zero));
+    assert!(simplest_between(ftos(2.5), ftos(2.75)) == Some(ftos(2.625)));
+    assert!(simplest_between(one, zero).is_none());
+
+    let x = simplest_between(ftos(0.3125), ftos(0.5)).unwrap();
+    assert!(x == ftos(0.375) && x.birthday() == 4);
+    for (lo, hi) in [
+        (zero, one),
+        (ftos(-2.0), ftos(-1.75)),
+        (ftos(1.5), ftos(4.0)),
+    ] {
+        let between = simplest_between(lo, hi).unwrap();
+        assert!(between == SurrealFinite::new(vec![lo], vec![hi]).unwrap());
+    }
+}
+
+#[test]
+fn hash_consistent_with_eq() {
+    use std::collections::HashSet;
+
+    let zero = ftos(0.0);
+    let one = SurrealFinite::new(vec![zero], vec![ftos(2.0)]).unwrap();
+    let also_one = SurrealFinite::new(vec![zero], vec![ftos(4.0)]).unwrap();
+    assert!(one == also_one && one.hash != also_one.hash);
+
+    let set: HashSet<SurrealFinite> = vec![one, also_one, ftos(1.0)].into_iter().collect();
+    assert!(set.len() == 1 && set.contains(&SurrealFinite::one()));
+
+    let set: HashSet<SurrealFinite> = generation(3).into_iter().chain(generation(2)).collect();
+    assert!(set.len() == generation(3).len());
+}
+
+#[test]
+fn consts() {
+    use super::consts;
+
+    assert!(consts::zero() == ftos(0.0) && consts::one() == ftos(1.0));
+    assert!(consts::neg_one() == ftos(-1.0) && consts::two() == ftos(2.0));
+    assert!(consts::half() == ftos(0.5));
+
+    // each is already in its simplest form, and so cached once
+    for x in [
+        consts::zero(),
+        consts::one(),
+        consts::neg_one(),
+        consts::half(),
+        consts::two(),
+    ] {
+        assert!(x.hash == x.canonicalize().hash && x.hash == ftos(x.stof()).hash);
+    }
+    assert!(consts::half().hash == consts::half().hash);
+}
+
+#[test]
+fn midpoint() {
+    let (zero, one) = (ftos(0.0), ftos(1.0));
+    assert!(zero.midpoint(one) == ftos(0.5));
+    assert!(ftos(2.0).midpoint(ftos(4.0)) == ftos(3.0));
+    assert!(ftos(-3.0).midpoint(ftos(0.25)) == ftos(-1.375));
+    assert!(ftos(0.75).midpoint(ftos(0.75)) == ftos(0.75));
+
+    for x in generation(3) {
+        for y in generation(3) {
+            let m = x.midpoint(y);
+            assert!(m == (x + y) * ftos(0.5) && m.hash == m.canonicalize().hash);
+        }
+    }
+}
+
+#[test]
+fn iter_options() {
+    assert!(ftos(0.5).iter_options().count() == 2);
+    assert!(ftos(0.0).iter_options().count() == 0);
+
+    let x = SurrealFinite::new(vec![ftos(-1.0), ftos(0.5)], vec![ftos(4.0)]).unwrap();
+    let options: Vec<SurrealFinite> = x.iter_options().collect();
+    assert!(options == vec![ftos(-1.0), ftos(0.5), ftos(4.0)]);
+    assert!(options.len() == x.len_left() + x.len_right());
+}
+
+#[test]
+fn structure() {
+    let x = SurrealFinite::new(vec![ftos(-1.0), ftos(0.5)], vec![ftos(4.0)]).unwrap();
+    let (left, right) = x.sets();
+    let structure = x.structure();
+    assert!(structure.left() == &left[..] && structure.right() == &right[..]); // no lock is held, so comparing is fine
+    assert!(structure.sets().0[1] + ftos(1.0) == ftos(1.5));
+
+    // every view shares the one cached copy
+    let again = x.structure();
+    assert!(structure.left().as_ptr() == again.left().as_ptr());
+    assert!(structure.right().as_ptr() == again.right().as_ptr());
+}
+
+#[test]
+fn equivalent_cached() {
+    let (zero, one) = (SurrealFinite::zero(), SurrealFinite::one());
+    let under_two = SurrealFinite::new(vec![zero], vec![ftos(2.0)]).unwrap();
+    let under_four = SurrealFinite::new(vec![zero], vec![ftos(4.0)]).unwrap();
+    assert!(under_two.hash != under_four.hash && under_two.hash != one.hash);
+
+    let equivalent: Vec<u64> = one.equivalent_cached().iter().map(|x| x.hash).collect();
+    assert!(equivalent.contains(&one.hash));
+    assert!(equivalent.contains(&under_two.hash) && equivalent.contains(&under_four.hash));
+    assert!(!equivalent.contains(&zero.hash));
+}
+
+#[test]
+fn describe() {
+    let half = ftos(0.5).describe();
+    assert!(half.contains("0.5") && half.contains("day 2"));
+    assert!(half == "0.5 (day 2, { 0 | 1 })");
+
+    assert!(ftos(-2.0).describe() == "-2 (day 2, { | -1 })");
+    let x = SurrealFinite::new(vec![ftos(-1.0), ftos(0.5)], vec![ftos(4.0)]).unwrap();
+    assert!(x.describe() == "1 (day 1, { -1 0.5 | 4 })");
+}
+
+#[test]
+fn pseudo_arithmetic() {
+    let (zero, one) = (ftos(0.0), ftos(1.0));
+    let pseudo = SurrealFinite {
+        hash: cache_insert(SurrealStructure::new(vec![one], vec![zero])), // { 1 | 0 }
+    };
+    assert!(pseudo.stof().is_nan());
+
+    let message = |result: std::thread::Result<SurrealFinite>| match result {
+        Err(error) => error.downcast::<String>().map(|s| *s).unwrap_or_default(),
+        Ok(_) => String::new(),
+    };
+    let sum = message(std::panic::catch_unwind(|| pseudo + one));
+    assert!(sum == "sum of a pseudo-number: left option 1 is not less than right option 0");
+    let negation = message(std::panic::catch_unwind(|| -pseudo));
+    assert!(negation.starts_with("negation of a pseudo-number: left option 0"));
+    let product = message(std::panic::catch_unwind(|| pseudo * ftos(0.5)));
+    assert!(product.contains(" of a pseudo-number: ")); // reported by the innermost operation that fails
+
+    assert!(ftos(1.5) + one == ftos(2.5)); // the memo tables are unaffected
+}
+
+#[test]
+fn poisoned_lock() {
+    let lock = RwLock::new(HashMap::new());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut memo = lock.write().unwrap();
+        memo.insert(1, 2);
+        std::panic!("failed with the lock held");
+    }));
+    assert!(result.is_err());
+
+    // a cache locked by a panicking computation keeps working, with what was written before the panic
+    assert!(lock.read().unwrap().get(&1) == Some(&2));
+    lock.write().unwrap().insert(3, 4);
+    assert!(lock.read().unwrap().len() == 2);
+}
+
+#[test]
+fn abs_diff() {
+    assert!(ftos(3.0).abs_diff(ftos(5.0)) == ftos(2.0));
+    assert!(ftos(5.0).abs_diff(ftos(3.0)) == ftos(2.0));
+    assert!(ftos(-0.5).abs_diff(ftos(0.75)) == ftos(1.25));
+    assert!(ftos(1.5).abs_diff(ftos(1.5)) == ftos(0.0));
+
+    assert!(ftos(0.3).stof_error(0.3) <= f64::EPSILON);
+    assert!(ftos(2.5).stof_error(3.0) == 0.5 && ftos(2.5).stof_error(2.0) == 0.5);
+    let third = div_approx(ftos(1.0), ftos(3.0)).unwrap();
+    assert!(third.stof_error(1.0 / 3.0) <= f64::EPSILON);
+}
+
+#[test]
+fn mul_int() {
+    assert!(ftos(0.5).mul_int(6) == ftos(3.0));
+    assert!(ftos(2.0).mul_int(-3) == ftos(-6.0));
+    assert!(ftos(-0.375).mul_int(0) == ftos(0.0));
+    assert!(ftos(-0.375).mul_int(-5) == ftos(1.875));
+
+    for x in generation(3) {
+        for n in -4..=4 {
+            assert!(x.mul_int(n) == x * integer(n) && integer(n) * x == x * integer(n));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "too large to convert")]
+fn mul_int_too_large() {
+    let _ = SurrealFinite::one().mul_int(1 << 40); // refused like integer(1 << 40), rather than doubled up to it
+}
+
+#[test]
+fn checked_mul() {
+    let (x, y) = (ftos(0.8125), ftos(-0.4375));
+    assert!(x.checked_mul(y, 2).is_none());
+    assert!(x.checked_mul(y, 64) == Some(ftos(0.8125 * -0.4375)));
+    assert!(x.checked_mul(y, 0) == Some(x * y)); // memoised now
+
+    assert!(ftos(3.0).checked_mul(ftos(-5.0), 0) == Some(ftos(-15.0)));
+    assert!(SurrealFinite::zero().checked_mul(ftos(0.5), 0) == Some(SurrealFinite::zero()));
+}
+
+#[test]
+fn pow() {
+    assert!(ftos(2.0).pow(10) == ftos(1024.0));
+    assert!(ftos(0.5).pow(3) == ftos(0.125));
+    assert!(ftos(-1.5).pow(2) == ftos(2.25));
+    assert!(ftos(3.0).pow(1) == ftos(3.0));
+    assert!(SurrealFinite::zero().pow(0) == SurrealFinite::one());
+    assert!(SurrealFinite::zero().pow(5) == SurrealFinite::zero());
+}
+
+#[test]
+fn shift() {
+    assert!((ftos(3.0) << 2) == ftos(12.0));
+    assert!((ftos(1.0) >> 3) == ftos(0.125));
+    assert!((ftos(0.375) << 3) == ftos(3.0));
+    assert!((ftos(-0.75) << 1) == ftos(-1.5));
+    assert!((ftos(-3.0) >> 2) == ftos(-0.75));
+    assert!((SurrealFinite::zero() << 100) == SurrealFinite::zero());
+
+    let mut x = ftos(1.5);
+    x <<= 2;
+    assert!(x == ftos(6.0));
+    x >>= 4;
+    assert!(x == ftos(0.375));
+
+    for x in generation(4) {
+        assert!((x << 1) == x + x);
+        assert!(((x >> 2) << 2) == x);
+        assert!((x >> 1).canonicalize().hash == (x >> 1).hash); // built in simplest form
+    }
+}
+
+#[test]
+#[should_panic(expected = "too large to convert")]
+fn shift_too_large() {
+    let _ = SurrealFinite::one() << 40; // refused rather than built 2^40 deep
+}
+
+#[test]
+fn min_max_clamp() {
+    assert!(ftos(2.0).min(ftos(-0.5)) == ftos(-0.5));
+    assert!(ftos(2.0).max(ftos(-0.5)) == ftos(2.0));
+    assert!(ftos(2.0).clamp(ftos(0.0), ftos(1.0)) == ftos(1.0));
+    assert!(ftos(-2.0).clamp(ftos(0.0), ftos(1.0)) == ftos(0.0));
+    assert!(ftos(0.25).clamp(ftos(0.0), ftos(1.0)) == ftos(0.25));
+}
+
+#[test]
+#[should_panic]
+fn clamp_inverted() {
+    ftos(0.5).clamp(ftos(1.0), ftos(0.0));
+}
+
+#[test]
+fn floor_ceil_round() {
+    let (zero, one) = (SurrealFinite::zero(), SurrealFinite::one());
+    assert!(ftos(0.5).floor() == zero);
+    assert!(ftos(0.5).ceil() == one);
+    assert!(ftos(2.5).round() == ftos(3.0));
+    assert!(ftos(2.25).round() == ftos(2.0));
+    assert!(ftos(-1.75).floor() == ftos(-2.0));
+    assert!(ftos(-1.75).ceil() == ftos(-1.0));
+    assert!(ftos(-1.75).round() == ftos(-2.0));
+
+    for x in generation(5) {
+        assert!(x.floor().stof() == x.stof().floor());
+        assert!(x.ceil().stof() == x.stof().ceil());
+        assert!(x.round().stof() == x.stof().round());
+    }
+}
+
+#[test]
+fn default() {
+    assert!(SurrealFinite::default() == SurrealFinite::zero());
+}
+
+#[test]
+fn sum_product() {
+    let one = SurrealFinite::one();
+    assert!(vec![one, one, one].into_iter().sum::<SurrealFinite>() == ftos(3.0));
+    assert!(
+        Vec::<SurrealFinite>::new()
+            .into_iter()
+            .sum::<SurrealFinite>()
+            == SurrealFinite::zero()
+    );
+    assert!(
+        Vec::<SurrealFinite>::new()
+            .into_iter()
+            .product::<SurrealFinite>()
+            == one
+    );
+
+    let v = generation(3);
+    assert!(v.iter().sum::<SurrealFinite>() == SurrealFinite::zero()); // symmetric about zero
+    assert!(v.iter().product::<SurrealFinite>() == SurrealFinite::zero());
+
+    let nonzero: Vec<SurrealFinite> = v
+        .into_iter()
+        .filter(|x| *x != SurrealFinite::zero())
+        .collect();
+    assert!(nonzero.iter().product::<SurrealFinite>() == ftos(-729.0 / 1024.0));
+    // -(3 * 2 * 1.5 * 1 * 0.75 * 0.5 * 0.25)^2
+}
+
+#[test]
+fn div_exact() {
+    assert!((SurrealFinite::one() / ftos(4.0)).stof() == 0.25);
+    assert!((ftos(3.0) / ftos(-8.0)).stof() == -0.375);
+    assert!(ftos(6.0) / ftos(1.5) == ftos(4.0));
+    assert!(div(SurrealFinite::one(), ftos(3.0)) == Err(DivError::NotDyadic));
+
+    // worked out from the operands' values, so a large quotient isn't searched for one integer at a time
+    assert!(div(ftos(3000.0), ftos(0.5)) == Ok(ftos(6000.0)));
+    assert!(div(ftos(-0.75), ftos(3.0)) == Ok(ftos(-0.25)));
+    assert!(div(ftos(0.5f64.powi(20)), ftos(8192.0)) == Err(DivError::NotDyadic)); // 33 binary places
+    assert!(div(ftos(0.5f64.powi(20)), ftos(4096.0)) == Ok(ftos(0.5f64.powi(32))));
+    assert!(div(SurrealFinite::one(), ftos(0.5f64.powi(30))) == Err(DivError::TooLarge));
+    assert!(div(ftos(3.0), ftos(6.0)) == Ok(ftos(0.5)));
+
+    let mut x = ftos(0.5);
+    x /= ftos(0.25);
+    assert!(x == ftos(2.0));
+}
+
+#[test]
+fn div_by_zero() {
+    let zero = SurrealFinite::zero();
+    assert!(div_approx(SurrealFinite::one(), zero) == Err(DivError::DivisionByZero));
+    assert!(div(SurrealFinite::one(), zero) == Err(DivError::DivisionByZero));
+    assert!(div_approx(ftos(3.0), ftos(4.0)).unwrap().stof() == 0.75);
+}
+
+#[test]
+fn rem() {
+    assert!(ftos(7.0) % ftos(3.0) == SurrealFinite::one());
+    assert!(ftos(7.0) % ftos(-3.0) == SurrealFinite::one());
+    assert!(ftos(-7.0) % ftos(3.0) == ftos(2.0));
+    assert!(ftos(-7.0) % ftos(-3.0) == ftos(2.0));
+    assert!(ftos(2.5) % ftos(1.0) == ftos(0.5));
+
+    let mut x = ftos(7.0);
+    x %= ftos(-3.0);
+    assert!(x == SurrealFinite::one());
+
+    for (a, b) in [
+        (7.0, 3.0),
+        (-7.0, 3.0),
+        (-7.0, -3.0),
+        (2.5, -1.0),
+        (-0.75, 0.5),
+        (1.0, 4.0),
+    ] {
+        let mut x = ftos(a);
+        x %= ftos(b);
+        assert!(x.hash == (ftos(a) % ftos(b)).hash);
+    }
+}
+
+#[test]
+#[should_panic(expected = "divisor of zero")]
+fn rem_by_zero() {
+    let _ = SurrealFinite::one() % SurrealFinite::zero();
+}
+
+#[test]
+fn cache_collisions() {
+    let zero = SurrealFinite::zero();
+    let slot = 0x5eed_5eed_5eed_5eed;
+
+    let a = cache_insert_at(slot, SurrealStructure::new(vec![zero], vec![]));
+    let b = cache_insert_at(slot, SurrealStructure::new(vec![], vec![zero]));
+    assert!(a != b);
+    assert!(cache_insert_at(slot, SurrealStructure::new(vec![zero], vec![])) == a);
+
+    assert!(cache_left(a)[..] == [zero] && cache_right(a).is_empty());
+    assert!(cache_left(b).is_empty() && cache_right(b)[..] == [zero]);
+}
+
+#[test]
+fn stable_hash() {
+    assert!(ftos(0.5).hash == ftos(0.5).hash);
+    assert!(
+        SurrealFinite::new(vec![SurrealFinite::zero()], vec![SurrealFinite::one()])
+            .unwrap()
+            .hash
+            == ftos(0.5).hash
     );
-    // println!(
-    //     "ω - 1 = {}",
-    //     SurrealInfinite::omega() - SurrealFinite::one().to_infinite()
-    // );
-    // println!(
-    //     "ϵ + 1 = {}",
-    //     SurrealInfinite::epsilon() + SurrealFinite::one().to_infinite()
-    // );
-    // println!(
-    //     "ω + ω = {}",
-    //     SurrealInfinite::omega() + SurrealInfinite::omega()
-    // );
-    // println!(
-    //     "ϵ * ω = {}",
-    //     SurrealInfinite::epsilon() * SurrealInfinite::omega()
-    // );
+    assert!(SurrealFinite::zero().hash == 0x8820_1fb9_60ff_6465); // the same in every run
+}
+
+#[test]
+fn concurrent_leq() {
+    let v = generation(4);
+    let handles: Vec<_> = (0..4)
+        .map(|t| {
+            let v = v.clone();
+            std::thread::spawn(move || {
+                for i in 0..v.len() {
+                    for j in 0..v.len() {
+                        assert!((v[i] <= v[j]) == (i <= j));
+                        assert!(v[i] + v[(j + t) % v.len()] == v[(j + t) % v.len()] + v[i]);
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn leq_deep() {
+    let zero = SurrealFinite::zero();
+    let mut x = zero;
+    let mut y = zero;
+    for _ in 0..5000 {
+        x = SurrealFinite::new(vec![x], vec![]).unwrap();
+        y = SurrealFinite::new(vec![zero, y], vec![]).unwrap();
+    }
+
+    assert!(x == y);
+    assert!(x < SurrealFinite::new(vec![y], vec![]).unwrap());
+}
+
+#[test]
+fn stof_memo() {
+    let v = generation(5);
+    let first: Vec<f64> = v.iter().map(|x| x.stof()).collect();
+    for _ in 0..100 {
+        assert!(v.iter().map(|x| x.stof()).collect::<Vec<f64>>() == first);
+    }
+}
+
+#[test]
+fn stof_deep() {
+    assert!(ftos(10000.0).stof() == 10000.0);
+    assert!(ftos(-10000.0).stof() == -10000.0);
+}
+
+#[test]
+fn birthday() {
+    let zero = SurrealFinite::zero();
+    assert!(zero.birthday() == 0);
+    assert!(SurrealFinite::one().birthday() == 1);
+    assert!((-SurrealFinite::one()).birthday() == 1);
+    assert!(ftos(0.5).birthday() == 2);
+    assert!(ftos(-2.25).birthday() == 5);
+    assert!(
+        SurrealFinite::new(vec![zero], vec![ftos(4.0)])
+            .unwrap()
+            .birthday()
+            == 1
+    );
+
+    for x in generation(3) {
+        assert!(x.birthday() < 4);
+    }
+}
+
+#[test]
+fn sign_expansion() {
+    assert!(SurrealFinite::zero().sign_expansion().is_empty());
+    assert!(SurrealFinite::one().sign_expansion() == vec![true]);
+    assert!(ftos(0.5).sign_expansion() == vec![true, false]);
+    assert!(ftos(-0.75).sign_expansion() == vec![false, true, false]);
+
+    for x in generation(5) {
+        let signs = x.sign_expansion();
+        assert!(signs.len() as u64 == x.birthday());
+        assert!(SurrealFinite::from_sign_expansion(&signs).hash == x.canonicalize().hash);
+    }
+
+    // ordered lexicographically, with - < end < +
+    let key = |x: &SurrealFinite| -> Vec<u8> {
+        let mut k: Vec<u8> = x
+            .sign_expansion()
+            .iter()
+            .map(|&s| if s { 2 } else { 0 })
+            .collect();
+        k.push(1);
+        k
+    };
+    let v = generation(4);
+    for x in &v {
+        for y in &v {
+            assert!((x < y) == (key(x) < key(y)));
+        }
+    }
+}
+
+#[test]
+fn canonicalize() {
+    let zero = SurrealFinite::zero();
+    let one = SurrealFinite::new(vec![zero], vec![ftos(4.0)]).unwrap();
+    assert!(one.canonicalize().hash == SurrealFinite::one().hash);
+
+    let half = SurrealFinite::new(vec![-ftos(3.0), zero], vec![ftos(0.75)]).unwrap();
+    assert!(half.canonicalize().hash == ftos(0.5).canonicalize().hash);
+
+    for x in generation(3) {
+        assert!(x.canonicalize() == x);
+        assert!(x.canonicalize().birthday() == x.birthday());
+    }
+}
+
+#[test]
+fn from_int() {
+    assert!(SurrealFinite::from(5i64) == ftos(5.0));
+    assert!(SurrealFinite::from(-3i32) == -ftos(3.0));
+    assert!(SurrealFinite::from(0u8) == SurrealFinite::zero());
+    assert!(SurrealFinite::from(7u64).hash == ftos(7.0).hash);
+    assert!(SurrealFinite::from(u8::MAX).stof() == 255.0);
+
+    // minimum values have no positive counterpart in their own type
+    assert!(SurrealFinite::from(i8::MIN) == ftos(-128.0));
+    assert!(SurrealFinite::from(i16::MIN).stof() == -32768.0);
+
+    // i64::MIN would be nested 2^63 deep, so it is refused straight away rather than never finishing
+    assert!(std::panic::catch_unwind(|| SurrealFinite::from(i64::MIN)).is_err());
+    assert!(std::panic::catch_unwind(|| SurrealFinite::from(u64::MAX)).is_err());
+    assert!(std::panic::catch_unwind(|| integer(-(1 << 24) - 1)).is_err());
+}
+
+#[test]
+fn compare_float() {
+    assert!(ftos(0.5) == 0.5 && 0.5 == ftos(0.5));
+    assert!(ftos(0.5) != 0.25 && 0.25 != ftos(0.5));
+    assert!(ftos(1.5) < 2.0 && 2.0 > ftos(1.5));
+    assert!(ftos(-1.5) > -2.0 && -2.0 < ftos(-1.5));
+    assert!(ftos(3.0) <= 3.0 && 3.0 >= ftos(3.0));
+    assert!(ftos(3.0).partial_cmp(&f64::NAN).is_none());
+}
+
+#[test]
+fn small_integers() {
+    assert!(integer(3) == ftos(3.0));
+    assert!(integer(3).hash == integer(3).hash);
+    assert!(integer(-64).hash == SurrealFinite::from(-64).hash);
+    assert!(integer(-200) == ftos(-200.0));
+    assert!(integer(65).iter_left().next().unwrap().hash == integer(64).hash);
+    assert!(integer(0).hash == SurrealFinite::zero().hash);
+}
+
+#[test]
+fn try_from_float() {
+    assert!(SurrealFinite::try_from(f64::NAN).is_err());
+    assert!(SurrealFinite::try_from(f64::NEG_INFINITY) == Err(TryFromFloatError::NotFinite));
+    assert!(SurrealFinite::try_from(1.0 / 3.0) == Err(TryFromFloatError::NotDyadic));
+    assert!(SurrealFinite::try_from(0.75).unwrap().stof() == 0.75);
+    assert!(SurrealFinite::try_from(-2.5).unwrap() == ftos(-2.5));
+
+    // rejected straight away rather than building every integer up to them
+    assert!(SurrealFinite::try_from(1e15) == Err(TryFromFloatError::TooLarge));
+    assert!(SurrealFinite::try_from(-f64::MAX) == Err(TryFromFloatError::TooLarge));
+    assert!(SurrealFinite::try_from(16777216.5) == Err(TryFromFloatError::TooLarge));
+}
+
+#[test]
+fn from_str() {
+    for x in generation(3) {
+        assert!(x.to_string().parse::<SurrealFinite>().unwrap() == x);
+    }
+
+    let zero = SurrealFinite::zero();
+    assert!("< | >".parse::<SurrealFinite>().unwrap() == zero);
+    assert!("<|>".parse::<SurrealFinite>().unwrap() == zero);
+    assert!("< < | > | 1 >".parse::<SurrealFinite>().unwrap() == ftos(0.5));
+    assert!("< -1 0.5 | < 1 | > >".parse::<SurrealFinite>().unwrap() == SurrealFinite::one());
+
+    assert!("< 1 | 0 >".parse::<SurrealFinite>() == Err(ParseSurrealError::NotANumber));
+    assert!("< 1 |".parse::<SurrealFinite>() == Err(ParseSurrealError::Syntax));
+    assert!("< a | >".parse::<SurrealFinite>() == Err(ParseSurrealError::Syntax));
+    assert!("< inf | >".parse::<SurrealFinite>() == Err(ParseSurrealError::Syntax));
+    assert!("< 1e15 | >".parse::<SurrealFinite>() == Err(ParseSurrealError::OutOfRange));
+    assert!("< | > < | >".parse::<SurrealFinite>() == Err(ParseSurrealError::Syntax));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_round_trip() {
+    use serde_json;
+
+    let one = serde_json::to_string(&SurrealFinite::one()).unwrap();
+    assert!(one == r#"{"left":[{"left":[],"right":[]}],"right":[]}"#);
+    assert!(serde_json::from_str::<SurrealFinite>(&one).unwrap() == SurrealFinite::one());
+
+    let half = serde_json::to_string(&ftos(0.5)).unwrap();
+    assert!(serde_json::from_str::<SurrealFinite>(&half).unwrap() == ftos(0.5));
+}
+
+#[test]
+#[cfg(feature = "num-traits")]
+fn num_traits_identities() {
+    use num_traits::{One, Zero};
+    use std::ops::Add;
+
+    fn sum_all<T: Zero + Add<Output = T>>(v: Vec<T>) -> T {
+        v.into_iter().fold(T::zero(), |acc, x| acc + x)
+    }
+
+    assert!(SurrealFinite::zero().is_zero());
+    assert!(!SurrealFinite::one().is_zero());
+    assert!(<SurrealFinite as One>::one() == SurrealFinite::one());
+    assert!(sum_all(vec![ftos(0.5), ftos(1.5), ftos(-3.0)]) == ftos(-1.0));
+}
+
+#[test]
+#[cfg(feature = "num-traits")]
+fn num_traits_signed() {
+    use num_traits::{Num, Signed};
+
+    assert!((-ftos(3.0)).abs() == ftos(3.0));
+    assert!(ftos(0.5).abs() == ftos(0.5));
+    assert!(ftos(0.0).signum() == SurrealFinite::zero());
+    assert!(ftos(-0.25).signum() == -SurrealFinite::one());
+    assert!(ftos(2.0).signum() == SurrealFinite::one());
+    assert!(ftos(1.0).abs_sub(&ftos(2.5)) == SurrealFinite::zero());
+    assert!(ftos(2.5).abs_sub(&ftos(1.0)) == ftos(1.5));
+    assert!(ftos(0.5).is_positive() && !ftos(0.5).is_negative());
+    assert!(!SurrealFinite::zero().is_positive() && !SurrealFinite::zero().is_negative());
+
+    assert!(SurrealFinite::from_str_radix("-1.1", 2).unwrap() == ftos(-1.5));
+    assert!(SurrealFinite::from_str_radix("0.1", 10).is_err());
+}
+
+#[test]
+#[cfg(feature = "num-traits")]
+fn num_traits_primitive() {
+    use num_traits::{FromPrimitive, ToPrimitive};
+
+    assert!(ftos(4.0).to_i64() == Some(4));
+    assert!(ftos(-3.0).to_i64() == Some(-3));
+    assert!(ftos(-3.0).to_u64().is_none());
+    assert!(ftos(2.5).to_i64().is_none() && ftos(2.5).to_u64().is_none());
+    assert!(ftos(-0.75).to_f64() == Some(-0.75));
+
+    assert!(SurrealFinite::from_f64(0.5).unwrap().stof() == 0.5);
+    assert!(SurrealFinite::from_f64(f64::NAN).is_none());
+    assert!(SurrealFinite::from_f64(1.0 / 3.0).is_none());
+    assert!(SurrealFinite::from_f64(1e15).is_none()); // too large to build, rather than hanging
+    assert!(SurrealFinite::from_i64(-7).unwrap() == integer(-7));
+    assert!(
+        SurrealFinite::from_i64(i64::MIN).is_none() && SurrealFinite::from_u64(u64::MAX).is_none()
+    );
+    assert!(SurrealFinite::from_u64(7).unwrap().to_u64() == Some(7));
+}
+
+#[test]
+#[cfg(feature = "num-traits")]
+fn num_traits_boundaries() {
+    use num_traits::ToPrimitive;
+
+    let min = SurrealFinite::from(i8::MIN);
+    assert!(min.to_i8() == Some(i8::MIN) && min.to_u8().is_none());
+    assert!(
+        (-min).to_i8().is_none() && (-min).to_i16() == Some(128) && (-min).to_u8() == Some(128)
+    );
+    assert!(SurrealFinite::from(u8::MAX).to_u8() == Some(u8::MAX));
+    assert!((SurrealFinite::from(u8::MAX) + ftos(1.0)).to_u8().is_none());
+
+    // i64::MIN is nested too deep to build, so the same boundary is checked one width down
+    let min = -SurrealFinite::from(i16::MIN);
+    assert!(min.to_i16().is_none() && min.to_i64() == Some(32768) && min.to_u16() == Some(32768));
+}
+
+#[test]
+fn dyadic_boundaries() {
+    // 2^-63 is the smallest power of two whose denominator fits in 64 bits
+    let tiny = |k: usize| {
+        let mut signs = vec![true];
+        signs.extend(vec![false; k]);
+        SurrealFinite::from_sign_expansion(&signs)
+    };
+    assert!(checked_dyadic_parts(&tiny(63)) == Some((1, 1 << 63)));
+    assert!(tiny(63).dyadic_parts() == (1, 1 << 63));
+    assert!(checked_dyadic_parts(&tiny(64)).is_none());
+    assert!(checked_dyadic_parts(&-tiny(70)).is_none());
+    assert!(checked_dyadic_parts(&(tiny(64) + ftos(1.0))).is_none());
+
+    // past 128 bits the walk stops tracking the value rather than overflowing
+    assert!(checked_dyadic_parts(&tiny(140)).is_none());
+    assert!((tiny(140) + ftos(1.0)).stof32() == 1.0);
+    assert!(tiny(126) == super::finite::construction::dyadic(1, 126));
+}
+
+#[test]
+#[should_panic(expected = "doesn't fit in 64-bit dyadic parts")]
+fn dyadic_parts_overflow() {
+    let mut signs = vec![true];
+    signs.extend(vec![false; 64]);
+    let _ = SurrealFinite::from_sign_expansion(&signs).dyadic_parts();
+}
+
+#[test]
+#[cfg(feature = "num-bigint")]
+fn big_rational() {
+    use num_bigint::BigInt;
+    use num_rational::BigRational;
+
+    let ratio = |n: i64, d: i64| BigRational::new(BigInt::from(n), BigInt::from(d));
+
+    assert!(ftos(0.375).to_big_rational() == ratio(3, 8));
+    assert!(SurrealFinite::from_big_rational(&ratio(3, 8)).unwrap() == ftos(0.375));
+    assert!(SurrealFinite::zero().to_big_rational() == ratio(0, 1));
+    for x in generation(4) {
+        assert!(SurrealFinite::from_big_rational(&x.to_big_rational()).unwrap() == x);
+    }
+    assert!(ftos(-2.75).to_big_rational() == ratio(-11, 4));
+
+    assert!(SurrealFinite::from_big_rational(&ratio(6, 4)).unwrap() == ftos(1.5)); // reduced to 3/2
+    assert!(SurrealFinite::from_big_rational(&ratio(1, 3)).is_none());
+    assert!(SurrealFinite::from_big_rational(&ratio(5, 12)).is_none());
+
+    // too deep to build, like the numbers FromPrimitive refuses
+    let tiny = BigRational::new(BigInt::from(1), BigInt::from(1) << 200);
+    assert!(SurrealFinite::from_big_rational(&tiny).is_none());
+    assert!(SurrealFinite::from_big_rational(&ratio(1 << 40, 1)).is_none());
+    let deep = BigRational::new(BigInt::from(3), BigInt::from(1) << 120);
+    assert!(
+        SurrealFinite::from_big_rational(&deep)
+            .unwrap()
+            .to_big_rational()
+            == deep
+    );
+}
+
+#[test]
+fn parse_omega() {
+    let omega: SurrealInfinite = "ω".parse().unwrap();
+    assert!(omega.approx_eq(&SurrealInfinite::omega(), 8));
+}
+
+#[test]
+fn parse_infinite() {
+    for named in [
+        SurrealInfinite::omega(),
+        -SurrealInfinite::omega(),
+        SurrealInfinite::epsilon(),
+        -SurrealInfinite::epsilon(),
+        ftos(5.0).to_infinite(),
+    ] {
+        let parsed: SurrealInfinite = named.to_string().parse().unwrap();
+        assert!(parsed.to_string() == named.to_string() && parsed.approx_eq(&named, 8));
+    }
+    assert!("< 0 | 1 >".parse::<SurrealInfinite>().unwrap().to_string() == "0.5");
+    assert!("Ω".parse::<SurrealInfinite>().err() == Some(ParseSurrealError::Syntax));
+    assert!("(ω - 1)".parse::<SurrealInfinite>().is_err());
+}
+
+#[test]
+fn omega() {
+    println!("ω = {}", SurrealInfinite::omega());
+    println!("ϵ = {}", SurrealInfinite::epsilon());
+    println!("-ω = {}", -SurrealInfinite::omega());
+    println!(
+        "2 - 1 = {}",
+        ftos(2.0).to_infinite() - SurrealFinite::one().to_infinite()
+    );
+
+    let five = ftos(2.0).to_infinite() + ftos(3.0).to_infinite();
+    assert!(five.to_string() == "5");
+    assert!(matches!(five.classify(0), Approximation::Exact(v) if v == ftos(5.0))); // known without looking at the sets
+    assert!((five.clone() - ftos(1.5).to_infinite()).to_string() == "3.5");
+    assert!((-five.clone() * ftos(0.5).to_infinite()).to_string() == "-2.5");
+
+    let one = SurrealFinite::one().to_infinite();
+    assert!(SurrealInfinite::omega().definitely_not_eq(&one));
+    assert!(one.definitely_not_eq(&SurrealInfinite::omega()));
+    assert!((-SurrealInfinite::omega()).definitely_not_eq(&one)); // by a right option instead
+    assert!(SurrealInfinite::omega().definitely_not_eq(&ftos(100.0).to_infinite()));
+    assert!(!one.definitely_not_eq(&one) && one.definitely_not_eq(&five));
+    assert!(!SurrealInfinite::omega().definitely_not_eq(&SurrealInfinite::omega())); // no finite value to separate them by
+    assert!(SurrealInfinite::epsilon().definitely_not_eq(&SurrealFinite::zero().to_infinite())); // its left option 0
+
+    let omega_minus_one = SurrealInfinite::omega() - SurrealFinite::one().to_infinite();
+    assert!(omega_minus_one.to_string() == "(ω - 1)");
+    assert!(format!("{:#}", omega_minus_one) == "< 0 1 2 3 4 ... | ω >");
+    for n in 1..8 {
+        // < 0 1 ... n-1 | ω >, with ω truncated to n + 1
+        assert!(omega_minus_one.to_finite(n).unwrap() == ftos(n as f64));
+    }
+
+    let epsilon_plus_one = SurrealInfinite::epsilon() + SurrealFinite::one().to_infinite();
+    assert!(format!("{:#}", epsilon_plus_one) == "< ϵ 1 | 1.5 1.25 1.125 1.0625 1.03125 ... >");
+    assert!(epsilon_plus_one.to_finite(1).unwrap() == SurrealFinite::one()); // 1 isn't reached yet: < ϵ | 1.5 >
+    for n in 2..8 {
+        // < ϵ 1 | 1.5 ... 1 + 2^-n >
+        let x = epsilon_plus_one.to_finite(n).unwrap();
+        assert!(x == ftos(1.0 + 0.5f64.powi(n as i32 + 1)));
+    }
+    println!(
+        "ω + ω = {}",
+        SurrealInfinite::omega() + SurrealInfinite::omega()
+    );
+    println!(
+        "ϵ * ω = {}",
+        SurrealInfinite::epsilon() * SurrealInfinite::omega()
+    );
+}
+
+#[test]
+fn infinite_names() {
+    let omega = SurrealInfinite::omega();
+    let epsilon = SurrealInfinite::epsilon();
+    assert!(omega.to_string() == "ω");
+    assert!(format!("{:#}", omega) == "< 1 2 3 4 5 ... | >");
+
+    assert!((omega.clone() + omega.clone()).to_string() == "(ω + ω)");
+    assert!((-omega.clone()).to_string() == "-ω");
+    assert!((epsilon.clone() * omega.clone()).to_string() == "(ϵ * ω)");
+    assert!((omega.clone() - ftos(0.5).to_infinite()).to_string() == "(ω - 0.5)");
+
+    let half_omega = SurrealInfinite::new(
+        Rc::new(|_, idx: usize| Some(integer(idx as i64).to_element())),
+        None,
+        Rc::new(|_, _| None),
+        None,
+        None,
+    )
+    .with_name("ω/2");
+    assert!(half_omega.to_string() == "ω/2");
+    assert!((half_omega.clone() + half_omega).to_string() == "(ω/2 + ω/2)");
+
+    let unnamed = SurrealInfinite::new(
+        Rc::new(|_, idx: usize| Some(integer(idx as i64).to_element())),
+        None,
+        Rc::new(|_, _| None),
+        None,
+        None,
+    );
+    assert!((omega + unnamed).to_string().starts_with('<')); // nothing to name it by
+}
+
+#[test]
+fn fmt_depth() {
+    let omega = SurrealInfinite::omega();
+    let sum = omega.clone() + omega.clone() + omega.clone();
+    assert!(sum.to_string() == "((ω + ω) + ω)");
+    let options = format!("{:#}", sum);
+    assert!(options.len() < 200 && options.matches("...").count() == 1); // five options, then ...
+
+    // without names, each option prints its own options
+    let nested = SurrealInfinite::new(
+        Rc::new(|_, idx: usize| {
+            let inner = SurrealInfinite::new(
+                Rc::new(move |_, i: usize| Some(integer((idx + i) as i64).to_element())),
+                None,
+                Rc::new(|_, _| None),
+                None,
+                None,
+            );
+            Some(inner.to_element())
+        }),
+        None,
+        Rc::new(|_, _| None),
+        None,
+        None,
+    );
+    assert!(nested
+        .to_string()
+        .starts_with("< < 0 1 2 3 4 ... | > < 1 2 3 4 5 ... | >"));
+    assert!(nested.clone().fmt_depth(1).to_string() == "< … … … … … ... | >");
+    assert!(nested.fmt_depth(0).to_string() == "…");
+}
+
+#[test]
+fn element_arithmetic() {
+    let (two, three) = (ftos(2.0).to_element(), ftos(3.0).to_element());
+    let known = ftos(3.0).to_infinite().to_element(); // infinite, but with a known value
+    let omega = SurrealInfinite::omega().to_element();
+
+    assert!((two.clone() + three.clone()).as_finite() == Some(ftos(5.0)));
+    assert!((two.clone() + known.clone()).as_finite() == Some(ftos(5.0)));
+    assert!((known.clone() + two.clone()).as_finite() == Some(ftos(5.0)));
+    assert!((known.clone() + known.clone()).as_finite() == Some(ftos(6.0)));
+    assert!((two.clone() - known.clone()).as_finite() == Some(ftos(-1.0)));
+    assert!((-two.clone()).as_finite() == Some(ftos(-2.0)));
+    assert!((two.clone() * known.clone()).as_finite() == Some(ftos(6.0)));
+
+    let sums = [
+        two.clone() + omega.clone(),
+        omega.clone() + two.clone(),
+        omega.clone() + omega.clone(),
+        omega.clone() - two.clone(),
+    ];
+    assert!(sums.iter().all(|x| x.as_finite().is_none()));
+    match sums[0] {
+        SurrealElement::Infinite(ref x) => assert!(x.to_string() == "(2 + ω)"),
+        _ => unreachable!(),
+    }
+    assert!((two.clone() + ftos(0.0).to_element()).as_finite() == Some(ftos(2.0)));
+    match ftos(0.0).to_element() + omega.clone() {
+        SurrealElement::Infinite(ref x) => assert!(x.to_string() == "ω"), // zero is skipped
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn infinite_options() {
+    let omega = SurrealInfinite::omega();
+    let left: Vec<SurrealFinite> = omega
+        .iter_left(4)
+        .iter()
+        .map(|x| x.as_finite().unwrap())
+        .collect();
+    assert!(left == vec![ftos(1.0), ftos(2.0), ftos(3.0), ftos(4.0)]);
+    assert!(omega.iter_right(4).is_empty());
+
+    let epsilon = SurrealInfinite::epsilon();
+    assert!(epsilon.iter_left(4).len() == 1);
+    assert!(epsilon.iter_right(2)[1].as_finite() == Some(ftos(0.25)));
+}
+
+#[test]
+fn approximations() {
+    let zero = SurrealFinite::zero();
+    let epsilon: Vec<SurrealFinite> = SurrealInfinite::epsilon()
+        .approximations()
+        .take(6)
+        .collect();
+    assert!(epsilon.iter().all(|x| *x > zero));
+    assert!(epsilon.windows(2).all(|pair| pair[0] > pair[1]));
+    assert!(epsilon[5] == ftos(1.0 / 128.0));
+
+    let omega: Vec<SurrealFinite> = SurrealInfinite::omega().approximations().take(4).collect();
+    assert!(omega.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let three = ftos(3.0).to_infinite();
+    assert!(three.approximations().take(3).all(|x| x == ftos(3.0)));
+}
+
+#[test]
+fn classify() {
+    let one = SurrealFinite::one();
+    assert!(one.to_infinite().classify(10) == Approximation::Exact(one));
+    assert!(SurrealInfinite::omega().classify(10) == Approximation::Truncated(ftos(11.0)));
+    assert!(SurrealInfinite::epsilon().classify(3) == Approximation::Truncated(ftos(0.0625)));
+
+    let two = SurrealInfinite::new(
+        Rc::new(|_, idx: usize| {
+            if idx < 2 {
+                Some(integer(idx as i64).to_element())
+            } else {
+                None
+            }
+        }),
+        None,
+        Rc::new(|_, _| None),
+        None,
+        None,
+    ); // < 0 1 | >, unlike from_finite without a value
+    assert!(two.classify(2) == Approximation::Exact(ftos(2.0)));
+    assert!(two.classify(1) == Approximation::Truncated(one));
+
+    let omega_minus_one = SurrealInfinite::omega() - one.to_infinite();
+    assert!(omega_minus_one.classify(10) == Approximation::Truncated(ftos(10.0))); // ω is cut off inside the right set
+
+    let pseudo = SurrealInfinite::new(
+        Rc::new(move |_, idx: usize| {
+            if idx == 0 {
+                Some(one.to_element())
+            } else {
+                None
+            }
+        }),
+        None,
+        Rc::new(|_, idx: usize| {
+            if idx == 0 {
+                Some(SurrealFinite::zero().to_element())
+            } else {
+                None
+            }
+        }),
+        None,
+        None,
+    ); // < 1 | 0 >
+    assert!(pseudo.classify(10) == Approximation::Diverges);
+    assert!((pseudo + one.to_infinite()).classify(10) == Approximation::Diverges);
+}
+
+#[test]
+fn surreal_macro() {
+    let zero = SurrealFinite::zero();
+    let one = SurrealFinite::one();
+
+    assert!(surreal! { | } == zero);
+    assert!(surreal! { 0 | } == one);
+    assert!(surreal! { | 0 } == -one);
+
+    let hand = SurrealFinite::new(vec![zero, one], vec![ftos(3.0)]).unwrap();
+    let x = surreal! { 0, 1 | 3 };
+    assert!(x == hand && x.iter_left().count() == 2 && x.iter_right().count() == 1);
+
+    let half = surreal! { 0 | 1 };
+    assert!(half == SurrealFinite::new(vec![zero], vec![one]).unwrap());
+    assert!(surreal! { -0.5, half | 1.5 } == one);
+    assert!(surreal! { one + one, 2.5 | } == ftos(3.0)); // any expression can be a member
+
+    let nested = surreal! { { | }, { { | } | } | { 2 | } };
+    let hand = SurrealFinite::new(vec![zero, one], vec![ftos(3.0)]).unwrap();
+    assert!(nested == hand);
+    assert!(nested.iter_left().collect::<Vec<_>>() == vec![zero, one]);
+}
+
+#[test]
+#[should_panic]
+fn surreal_macro_invalid() {
+    let _ = surreal! { 1 | 0 };
+}
+
+#[test]
+fn games() {
+    let zero = SurrealGame::zero();
+    let one = SurrealGame::from(SurrealFinite::one());
+    let star = SurrealGame::new(vec![zero.clone()], vec![zero.clone()]);
+
+    assert!(star.cmp_game(&zero) == GameOrdering::Confused);
+    assert!(zero.cmp_game(&star) == GameOrdering::Confused);
+    assert!(star.cmp_game(&star) == GameOrdering::Equal);
+    assert!(star.cmp_game(&one) == GameOrdering::Less); // * is infinitesimal
+    assert!(star.cmp_game(&-one.clone()) == GameOrdering::Greater);
+    assert!(star.try_into_number().is_none());
+    assert!(star.to_string() == "{ 0 | 0 }");
+
+    assert!(star.clone() + star.clone() == zero); // the second player wins
+    assert!(-star.clone() == star);
+    assert!((star.clone() + one.clone()).try_into_number().is_none());
+    assert!((star.clone() + one.clone()).cmp_game(&one) == GameOrdering::Confused);
+
+    // numbers behave exactly as they do as SurrealFinite
+    let v = generation(2);
+    for &x in &v {
+        let g = SurrealGame::from(x);
+        assert!(g.try_into_number().unwrap() == x);
+        assert!(g.to_string() == x.stof().to_string());
+        for &y in &v {
+            assert!(g.cmp_game(&SurrealGame::from(y)) == x.compare(&y));
+            assert!(
+                (g.clone() + SurrealGame::from(y))
+                    .try_into_number()
+                    .unwrap()
+                    == x + y
+            );
+        }
+    }
+}
+
+#[test]
+fn compare() {
+    let v = generation(3);
+    for x in &v {
+        for y in &v {
+            let o = x.compare(y);
+            assert!(o != SurrealOrdering::Confused);
+            assert!(o == SurrealOrdering::from(x.cmp(y)));
+            assert!(o == SurrealGame::from(*x).compare(&SurrealGame::from(*y)));
+        }
+    }
+
+    assert!(ftos(0.5).compare(&ftos(0.25)) == SurrealOrdering::Greater);
+    assert!(SurrealGame::star().compare(&SurrealGame::zero()) == SurrealOrdering::Confused);
+}
+
+#[test]
+fn star_up_down() {
+    let zero = SurrealGame::zero();
+    let (star, up, down) = (SurrealGame::star(), SurrealGame::up(), SurrealGame::down());
+    assert!(star == SurrealGame::new(vec![zero.clone()], vec![zero.clone()]));
+
+    assert!(up.clone() + down.clone() == zero);
+    assert!(star.clone() + star.clone() == zero);
+    assert!(-up.clone() == down);
+
+    assert!(up.cmp_game(&zero) == GameOrdering::Greater);
+    assert!(down.cmp_game(&zero) == GameOrdering::Less);
+    assert!(up.cmp_game(&star) == GameOrdering::Confused);
+    assert!((up.clone() + star.clone()).cmp_game(&zero) == GameOrdering::Confused);
+    assert!((up.clone() + up.clone() + star).cmp_game(&zero) == GameOrdering::Greater);
+
+    let tiny = SurrealGame::from(ftos(0.0625));
+    assert!(
+        up.cmp_game(&tiny) == GameOrdering::Less && down.cmp_game(&-tiny) == GameOrdering::Greater
+    );
+    assert!(up.to_string() == "{ 0 | { 0 | 0 } }");
+}
+
+#[test]
+fn nim_value() {
+    let zero = SurrealGame::zero();
+    let star = SurrealGame::star();
+    assert!(zero.nim_value() == Some(0));
+    assert!(star.nim_value() == Some(1));
+
+    let star2 = SurrealGame::new(
+        vec![zero.clone(), star.clone()],
+        vec![star.clone(), zero.clone()],
+    );
+    assert!(star2.nim_value() == Some(2));
+    let star3 = SurrealGame::new(
+        vec![zero.clone(), star.clone(), star2.clone()],
+        vec![zero.clone(), star.clone(), star2.clone()],
+    );
+    assert!(star3.nim_value() == Some(3));
+    assert!((star.clone() + star2.clone()).nim_value() == Some(3)); // nim sums are xors
+    assert!((star2.clone() + star3.clone()).nim_value() == Some(1));
+
+    let gap = SurrealGame::new(vec![zero.clone(), star2.clone()], vec![star2, zero]);
+    assert!(gap.nim_value() == Some(1)); // the mex skips over 2
+
+    assert!(SurrealGame::up().nim_value().is_none());
+    assert!(SurrealGame::from(SurrealFinite::one())
+        .nim_value()
+        .is_none());
+    assert!(
+        SurrealGame::new(vec![star.clone()], vec![SurrealGame::up()])
+            .nim_value()
+            .is_none()
+    );
+}
+
+#[test]
+fn set_lengths() {
+    let half = ftos(0.5);
+    assert!(half.len_left() == 1 && half.len_right() == 1);
+    assert!(half.sets() == (vec![SurrealFinite::zero()], vec![SurrealFinite::one()]));
+
+    let zero = SurrealFinite::zero();
+    assert!(zero.len_left() == 0 && zero.len_right() == 0);
+    assert!(ftos(-2.0).len_left() == 0 && ftos(-2.0).len_right() == 1);
+
+    let wide = SurrealFinite::new(
+        vec![ftos(-1.0), zero, ftos(0.25)],
+        vec![ftos(3.0), ftos(2.0)],
+    )
+    .unwrap();
+    assert!(wide.len_left() == 3 && wide.len_right() == 2);
+    let (left, right) = wide.sets();
+    assert!(
+        left == wide.iter_left().collect::<Vec<_>>()
+            && right == wide.iter_right().collect::<Vec<_>>()
+    );
+    assert!(wide.canonicalize().sets() == (vec![zero], vec![])); // the value is 1
+}
+
+#[test]
+fn debug_structure() {
+    let half = ftos(0.5);
+    let debug = format!("{:?}", half);
+    assert!(debug.starts_with("SurrealFinite(0.5 = { { | } | { { | } | } }, hash: 0x"));
+    assert!(debug.ends_with(&format!("{:016x})", half.hash)));
+    assert!(format!("{:?}", half) != half.to_string());
+
+    let deep = format!("{:?}", ftos(5.0));
+    assert!(deep.starts_with("SurrealFinite(5 = { { { { ... | } | } | } | }, hash: "));
+
+    assert!(
+        format!("{:?}", Some(SurrealFinite::zero())).starts_with("Some(SurrealFinite(0 = { | }")
+    );
+}
+
+#[test]
+fn binary_string() {
+    assert!(ftos(0.375).to_binary_string() == "0.011");
+    assert!(SurrealFinite::zero().to_binary_string() == "0");
+    assert!(ftos(5.0).to_binary_string() == "101");
+    assert!(ftos(-1.0).to_binary_string() == "-1");
+    assert!(ftos(-2.625).to_binary_string() == "-10.101");
+    assert!(ftos(0.5).to_binary_string() == "0.1");
+
+    for x in generation(5) {
+        let b = x.to_binary_string();
+        assert!(b.contains('.') != x.is_integer());
+        let (int, frac) = b
+            .trim_start_matches('-')
+            .split_once('.')
+            .unwrap_or((b.trim_start_matches('-'), ""));
+        let mut value = i64::from_str_radix(int, 2).unwrap() as f64;
+        for (i, c) in frac.chars().enumerate() {
+            value += if c == '1' {
+                0.5f64.powi(i as i32 + 1)
+            } else {
+                0.0
+            };
+        }
+        assert!(if b.starts_with('-') { -value } else { value } == x.stof());
+    }
+}
+
+#[test]
+fn recip() {
+    let zero = SurrealFinite::zero();
+    assert!(ftos(4.0).recip() == Some(ftos(0.25)));
+    assert!(ftos(0.25).recip() == Some(ftos(4.0)));
+    assert!(ftos(-8.0).recip() == Some(ftos(-0.125)));
+    assert!(SurrealFinite::one().recip() == Some(SurrealFinite::one()));
+    assert!(ftos(3.0).recip().is_none());
+    assert!(ftos(0.75).recip().is_none());
+    assert!(zero.recip().is_none());
+    assert!(ftos(0.5f64.powi(30)).recip().is_none()); // 2^30 is too deep to build
+    assert!((-ftos(0.5f64.powi(30))).recip().is_none());
+
+    for x in generation(4) {
+        if let Some(y) = x.recip() {
+            assert!(x * y == SurrealFinite::one() && y.recip() == Some(x));
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "num-traits")]
+fn num_traits_inv() {
+    use num_traits::Inv;
+
+    assert!(ftos(2.0).inv() == Some(ftos(0.5)));
+    assert!(ftos(-6.0).inv().is_none());
+}
+
+#[test]
+fn to_element() {
+    let one = SurrealFinite::one();
+    assert!(one.to_element().as_finite().unwrap().hash == one.hash);
+    assert!(SurrealElement::Infinite(SurrealInfinite::omega())
+        .as_finite()
+        .is_none());
+    assert!(one.to_infinite().to_finite(10).unwrap() == one);
+}
+
+#[test]
+fn element_cmp() {
+    let (three, half) = (ftos(3.0).to_element(), ftos(0.5).to_element());
+    let (omega, epsilon) = (
+        SurrealInfinite::omega().to_element(),
+        SurrealInfinite::epsilon().to_element(),
+    );
+
+    assert!(half < three && three == ftos(3.0).to_element());
+    assert!(three < omega && omega.partial_cmp(&three) == Some(Ordering::Greater));
+    assert!(omega == SurrealInfinite::omega().to_element());
+    assert!(epsilon > SurrealFinite::zero().to_element());
+    assert!(epsilon < ftos(0.001).to_element() && epsilon < half);
+    assert!(SurrealElement::Infinite(ftos(1.5).to_infinite()) == ftos(1.5).to_element());
+}
+
+#[test]
+#[allow(deprecated)]
+fn coerce_finite() {
+    assert!(ftos(0.5).to_element().coerce_finite() == ftos(0.5));
+}
+
+#[test]
+#[should_panic]
+#[allow(deprecated)]
+fn coerce_infinite() {
+    SurrealInfinite::omega().to_element().coerce_finite();
+}
+
+#[test]
+fn approx_eq() {
+    let omega = SurrealInfinite::omega();
+    assert!(omega.approx_eq(&SurrealInfinite::omega(), 10));
+    assert!(!omega.approx_eq(
+        &(SurrealInfinite::omega() + SurrealFinite::one().to_infinite()),
+        10
+    ));
+    assert!(ftos(0.5)
+        .to_infinite()
+        .approx_eq(&ftos(0.5).to_infinite(), 1));
+}
+
+#[test]
+fn infinite_leq() {
+    let omega = SurrealInfinite::omega();
+    let three = ftos(3.0).to_infinite();
+    assert!(omega.partial_cmp_approx(&three, 1) == Some(Ordering::Less));
+    assert!(omega.partial_cmp_approx(&three, 5) == Some(Ordering::Greater));
+    assert!(omega.partial_cmp_approx(&omega, 5) == Some(Ordering::Equal));
+    assert!(three.leq(&omega, 5) && !omega.leq(&three, 5));
+
+    let epsilon = SurrealInfinite::epsilon();
+    assert!(epsilon.leq(&ftos(0.0001).to_infinite(), 20));
+    assert!(epsilon.partial_cmp_approx(&ftos(0.0001).to_infinite(), 20) == Some(Ordering::Less));
+}
+
+#[test]
+fn infinite_assign() {
+    let one = SurrealFinite::one();
+    let mut x = SurrealInfinite::omega();
+    x -= one.to_infinite();
+    assert!(x.to_finite(10).unwrap() == ftos(10.0)); // < 0 1 ... 9 | ω >, with ω truncated to 11
+    assert!(SurrealInfinite::omega().to_finite(10).unwrap() == ftos(11.0));
+
+    x += one.to_infinite();
+    assert!(x.to_finite(4).unwrap() > ftos(4.0));
+
+    let mut y = ftos(1.5).to_infinite();
+    y *= ftos(-2.0).to_infinite();
+    assert!(y.to_string() == "-3");
+    y += ftos(0.5).to_infinite();
+    assert!(y.to_finite(10).unwrap() == ftos(-2.5));
+}
+
+#[test]
+fn zip_dedup() {
+    let range = |start: i64, end: i64| -> Rc<dyn SurrealIterator> {
+        Rc::new(SurrealBasicSet::new(
+            Rc::new(move |_, idx: usize| {
+                let n = start + idx as i64;
+                if n < end {
+                    Some(integer(n).to_element())
+                } else {
+                    None
+                }
+            }),
+            None,
+        ))
+    };
+
+    let zip = SurrealZipSet::new(vec![range(0, 5), range(3, 8)]);
+    let taken = zip.take(20);
+    assert!(taken.len() == 8); // 0 to 7, each once
+    for (i, x) in taken.iter().enumerate() {
+        assert!(taken[..i].iter().all(|y| y != x));
+    }
+    assert!(zip.take(4).len() == 4);
+}
+
+#[test]
+fn take_memo() {
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let set = SurrealBasicSet::new(
+        Rc::new(move |_, idx: usize| {
+            counter.set(counter.get() + 1);
+            if idx < 12 {
+                Some(integer(idx as i64).to_element())
+            } else {
+                None
+            }
+        }),
+        None,
+    );
+
+    let ten = set.take(10);
+    assert!(calls.get() == 10);
+    assert!(set.take(5) == ten[..5]);
+    assert!(set.take(10) == ten);
+    assert!(calls.get() == 10);
+
+    let all = set.take(20);
+    assert!(all.len() == 12 && all[..10] == ten[..]);
+    assert!(calls.get() == 13); // the end of the set is only found once
+    assert!(set.take(20) == all);
+    assert!(calls.get() == 13);
+
+    let zip = SurrealZipSet::new(vec![Rc::new(set)]);
+    assert!(zip.take(3) == all[..3]);
+    assert!(zip.take(12) == all);
+}
+
+#[test]
+fn shared_memo() {
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let omega = SurrealInfinite::new(
+        Rc::new(move |_, idx: usize| {
+            counter.set(counter.get() + 1);
+            Some(integer(idx as i64 + 1).to_element())
+        }),
+        None,
+        Rc::new(|_, _| None),
+        None,
+        Some(String::from("ω")),
+    );
+
+    let one = ftos(1.0).to_infinite();
+    let a = omega.clone() + one.clone();
+    let b = a.clone() + one.clone();
+    let c = b.clone() + one;
+    let approx = c.to_finite(5);
+    assert!(approx.is_some());
+    let after_c = calls.get();
+    assert!(after_c <= 6);
+
+    // the sums share the sets of ω, so their options were already computed
+    assert!(a.to_finite(5).is_some() && b.to_finite(5).is_some());
+    assert!(omega.to_finite(5).unwrap() == ftos(6.0) && c.to_finite(5) == approx);
+    assert!(calls.get() == after_c);
+}
+
+#[test]
+fn take_past_end() {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let counter = calls.clone();
+    let set = SurrealBasicSet::new(
+        Rc::new(move |_, idx: usize| {
+            counter.borrow_mut().push(idx);
+            if idx < 3 {
+                Some(integer(idx as i64).to_element())
+            } else {
+                None
+            }
+        }),
+        None,
+    );
+
+    assert!(set.take(5).len() == 3);
+    assert!(set.take(8).len() == 3 && set.take(100).len() == 3);
+    assert!(*calls.borrow() == vec![0, 1, 2, 3]); // never called past the None at index 3
+
+    let zip = SurrealZipSet::new(vec![Rc::new(set)]);
+    assert!(zip.take(5).len() == 3 && zip.take(50).len() == 3);
+    assert!(calls.borrow().len() == 4);
+}
+
+#[test]
+fn infinite_mul() {
+    println!(
+        "ω * ω = {}",
+        SurrealInfinite::omega() * SurrealInfinite::omega()
+    );
+
+    let product = ftos(1.5).to_infinite() * ftos(-2.0).to_infinite();
+    assert!(product.to_finite(10).unwrap() == ftos(1.5) * ftos(-2.0));
+    assert!(product.to_string() == "-3");
 }
 
 // todo: rem, assign, infinite, fmt
@@ -0,0 +1,55 @@
+// loading a dump into a cleared cache invalidates every existing number, so this runs in its own process
+
+#![cfg(all(feature = "serde", feature = "std"))]
+
+extern crate surreal;
+
+use std::io::ErrorKind;
+
+use surreal::{cache_stats, clear_caches, dump_cache, ftos, generation, load_cache};
+
+#[test]
+fn dump_and_load_round_trip() {
+    let values: Vec<f64> = generation(5).iter().map(|x| x.stof()).collect();
+    let structures = cache_stats().structures;
+
+    let mut dump = Vec::new();
+    dump_cache(&mut dump).unwrap();
+
+    clear_caches();
+    assert!(cache_stats().structures == 0);
+    load_cache(&dump[..]).unwrap();
+    assert!(cache_stats().structures == structures);
+
+    // building the same numbers again finds them already interned
+    let rebuilt = generation(5);
+    assert!(rebuilt.iter().map(|x| x.stof()).eq(values.iter().cloned()));
+    assert!(cache_stats().structures == structures);
+    assert!(ftos(0.75).birthday() == 3);
+
+    // loading on top of an existing cache adds nothing new
+    load_cache(&dump[..]).unwrap();
+    assert!(cache_stats().structures == structures);
+
+    // the order structures are written in may differ between dumps, but they load the same
+    let mut again = Vec::new();
+    dump_cache(&mut again).unwrap();
+    clear_caches();
+    load_cache(&again[..]).unwrap();
+    assert!(cache_stats().structures == structures);
+    assert!(ftos(0.375).birthday() == 4 && cache_stats().structures == structures);
+}
+
+#[test]
+fn load_rejects_invalid_data() {
+    let error = load_cache(&b"not a dump at all"[..]).unwrap_err();
+    assert!(error.kind() == ErrorKind::InvalidData);
+
+    let dump = br#"{"version":1,"structures":[[[],[]],[[5],[]]]}"#; // refers to a structure that doesn't exist
+    assert!(load_cache(&dump[..]).unwrap_err().kind() == ErrorKind::InvalidData);
+
+    let newer = br#"{"version":2,"structures":[]}"#;
+    assert!(load_cache(&newer[..]).unwrap_err().kind() == ErrorKind::InvalidData);
+
+    assert!(load_cache(&dump[..20]).unwrap_err().kind() == ErrorKind::UnexpectedEof);
+}
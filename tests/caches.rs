@@ -0,0 +1,25 @@
+// clearing the global caches invalidates every existing number, so this runs in its own process
+
+extern crate surreal;
+
+use std::panic;
+
+use surreal::{cache_stats, clear_caches, ftos, CacheStats, SurrealFinite};
+
+#[test]
+fn clear_caches_empties_everything() {
+    let x = ftos(1.5) * ftos(0.5) + (-ftos(2.0));
+    assert!(x == ftos(-1.25) && x.birthday() == 4 && x.stof() == -1.25);
+
+    let stats = cache_stats();
+    assert!(stats.structures > 0 && stats.leq > 0 && stats.birthday > 0 && stats.stof > 0);
+    assert!(stats.add > 0 && stats.neg > 0 && stats.mul > 0);
+
+    clear_caches();
+    assert!(cache_stats() == CacheStats::default());
+
+    // stale numbers panic, but the caches remain usable
+    assert!(panic::catch_unwind(|| x.stof()).is_err());
+    assert!(format!("{:?}", x).starts_with("SurrealFinite(cleared, hash: "));
+    assert!(SurrealFinite::one() + SurrealFinite::one() == ftos(2.0));
+}
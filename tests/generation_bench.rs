@@ -0,0 +1,31 @@
+// a benchmark of the operations that read sets out of the cache most often, in its own process so its timing isn't shared with other tests
+
+extern crate surreal;
+
+use std::time::{Duration, Instant};
+
+use surreal::{generation, leq};
+
+#[test]
+fn day_six_comparisons() {
+    let v = generation(6);
+    assert!(v.len() == 127);
+
+    let start = Instant::now();
+    for x in &v {
+        for y in &v {
+            assert!(leq(x, y) == (x.stof() <= y.stof()));
+        }
+    }
+
+    let small = generation(4);
+    for x in &small {
+        for y in &small {
+            assert!((*x + *y).stof() == x.stof() + y.stof());
+            assert!((*x * *y).stof() == x.stof() * y.stof());
+        }
+    }
+    let elapsed = start.elapsed();
+    println!("day 6 comparisons and day 4 arithmetic took {:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(60)); // generous, for unoptimised builds
+}
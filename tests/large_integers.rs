@@ -0,0 +1,26 @@
+// a million-deep integer fills the global cache with as many structures, so this runs in its own process
+
+extern crate surreal;
+
+use std::time::{Duration, Instant};
+
+use surreal::ftos;
+
+#[test]
+fn ftos_large_integers() {
+    let start = Instant::now();
+    assert!(ftos(1_000_000.0).stof() == 1_000_000.0);
+    assert!(ftos(-1_000_000.5).stof() == -1_000_000.5);
+    assert!(ftos(999_999.75).stof() == 999_999.75);
+    assert!(start.elapsed() < Duration::from_secs(60)); // generous, for unoptimised builds
+}
+
+#[test]
+fn ftos_small_integers() {
+    for n in -3i32..=3 {
+        let x = ftos(f64::from(n));
+        assert!(x.birthday() == u64::from(n.unsigned_abs()) && x.stof() == f64::from(n));
+    }
+    assert!(ftos(0.75).sign_expansion() == vec![true, false, true]);
+    assert!(ftos(-2.5).sign_expansion() == vec![false, false, false, true]);
+}
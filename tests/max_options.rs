@@ -0,0 +1,39 @@
+// the option limit is global, so this runs in its own process
+
+extern crate surreal;
+
+use std::panic;
+
+use surreal::{ftos, max_options, set_max_options, ParseSurrealError, SurrealFinite, TryNewError};
+
+#[test]
+fn oversized_constructions_fail() {
+    assert!(max_options() == usize::MAX);
+    let (a, b) = (ftos(0.75), ftos(-1.5));
+    let product = a * b; // built before the limit, for comparison
+
+    set_max_options(3);
+    assert!(max_options() == 3);
+    let small = vec![ftos(0.0), ftos(0.5)];
+    assert!(SurrealFinite::try_new(small.clone(), vec![ftos(1.0)]) == Ok(ftos(0.75)));
+
+    let error = SurrealFinite::try_new(small.clone(), vec![ftos(1.0), ftos(2.0)]).unwrap_err();
+    assert!(
+        error
+            == TryNewError::TooManyOptions {
+                options: 4,
+                limit: 3
+            }
+    );
+    assert!(error.to_string() == "4 options exceed the limit of 3");
+    assert!(SurrealFinite::new(small, vec![ftos(1.0), ftos(2.0)]).is_none());
+    assert!("< 0 1 | 2 3 >".parse::<SurrealFinite>() == Err(ParseSurrealError::TooManyOptions));
+
+    // the raw sets of arithmetic hold every combination of options, so they exceed the limit before being simplified
+    let panic = panic::catch_unwind(|| ftos(0.625) * ftos(-1.75)).unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.contains(" is too large: ") && message.ends_with("exceed the limit of 3"));
+
+    set_max_options(usize::MAX);
+    assert!(a * b == product && ftos(0.625) * ftos(-1.75) == ftos(-1.09375));
+}
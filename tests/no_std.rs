@@ -0,0 +1,24 @@
+// uses the crate the way a no_std user would, with only core and alloc; run with --no-default-features to build the library without std as well
+#![no_std]
+
+extern crate alloc;
+extern crate std; // only for the test harness
+extern crate surreal;
+
+use alloc::string::ToString;
+use alloc::vec;
+
+use surreal::{ftos, generation, integer, SurrealFinite, SurrealInfinite};
+
+#[test]
+fn core_api_without_std() {
+    let x = SurrealFinite::new(vec![ftos(0.5)], vec![SurrealFinite::one()]).unwrap();
+    assert!(x.stof() == 0.75);
+    assert!(x + -ftos(0.25) == ftos(0.5));
+    assert!(x * integer(4) == ftos(3.0));
+    assert!(generation(2).len() == 7);
+    assert!(x.to_string().parse::<SurrealFinite>().unwrap() == x);
+
+    let omega = SurrealInfinite::omega();
+    assert!(omega.to_finite(3).unwrap() == integer(4));
+}
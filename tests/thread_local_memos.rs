@@ -0,0 +1,53 @@
+// a benchmark of the per-thread memo tables, in its own process so its timing and its clears aren't shared with other tests
+
+#![cfg(feature = "thread-local-memos")]
+
+extern crate surreal;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use surreal::{cache_stats, clear_caches, generation, leq};
+
+fn check_day_five() {
+    let v = generation(5);
+    for x in &v {
+        for y in &v {
+            assert!(leq(x, y) == (x.stof() <= y.stof()));
+        }
+    }
+
+    let small = generation(3);
+    for x in &small {
+        for y in &small {
+            assert!((*x + *y).stof() == x.stof() + y.stof());
+            assert!((*x * *y).stof() == x.stof() * y.stof());
+            assert!((-*x).stof() == -x.stof());
+        }
+    }
+}
+
+#[test]
+fn single_threaded() {
+    let start = Instant::now();
+    check_day_five();
+    assert!(cache_stats().leq > 0 && cache_stats().add > 0);
+
+    // answered again from the memo tables, then again after they are cleared
+    check_day_five();
+    clear_caches();
+    assert!(cache_stats().leq == 0 && cache_stats().add == 0);
+    check_day_five();
+
+    // a new thread starts with empty memo tables but the same numbers
+    thread::spawn(|| {
+        assert!(cache_stats().leq == 0);
+        check_day_five();
+    })
+    .join()
+    .unwrap();
+
+    let elapsed = start.elapsed();
+    println!("day 5 comparisons and day 3 arithmetic took {:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(60)); // generous, for unoptimised builds
+}
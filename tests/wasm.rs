@@ -0,0 +1,35 @@
+// on wasm without atomics the caches are RefCells rather than locks; check that with
+// `cargo test --target wasm32-wasip1 --test wasm`, given a WASI runner such as wasmtime,
+// and that the library builds for the browser with `cargo build --target wasm32-unknown-unknown`
+
+extern crate surreal;
+
+use surreal::{cache_stats, div, ftos, generation, integer, SurrealFinite, SurrealInfinite};
+
+#[test]
+fn single_threaded_arithmetic() {
+    let x = ftos(1.5) * ftos(0.5) + (-ftos(2.0));
+    assert!(x == ftos(-1.25) && x.birthday() == 4 && x.stof() == -1.25);
+    assert!(integer(3).mul_int(-4) == integer(-12));
+    assert!(div(ftos(3.0), ftos(4.0)) == Ok(ftos(0.75)));
+
+    // the memos are read and written in turn as the operations recurse
+    let v = generation(3);
+    for &a in &v {
+        for &b in &v {
+            assert!((a + b).stof() == a.stof() + b.stof());
+            assert!((a * b).stof() == a.stof() * b.stof());
+        }
+    }
+    let stats = cache_stats();
+    assert!(stats.structures > 0 && stats.add > 0 && stats.mul > 0);
+
+    let structure = SurrealFinite::one().structure();
+    assert!(structure.left().len() == 1 && structure.right().is_empty());
+    assert!(structure.left()[0] == SurrealFinite::zero()); // holds no borrow of the cache
+
+    let omega = SurrealInfinite::omega();
+    assert!((omega.clone() + ftos(1.0).to_infinite())
+        .to_finite(3)
+        .is_some());
+}